@@ -1,11 +1,16 @@
 use mathml_renderer::ast::Node;
 use mathml_renderer::attribute::{
-    FracAttr, MathSpacing, MathVariant, OpAttr, Size, Style, TextTransform,
+    FontChange, FontSize, FracAttr, MathVariant, OpAttr, Size, Style, TextTransform,
 };
 use mathml_renderer::ops::{self, Rel};
 
 use crate::predefined;
-use crate::token::Token;
+use crate::token::{Braket, Infix, Limits, Token};
+use crate::OperatorLocale;
+
+/// The width of `\,`, in em. The parser looks for it to find differentials and
+/// digit groups.
+pub(crate) const THIN_SPACE_WIDTH: &str = "0.1667";
 
 static COMMANDS: phf::Map<&'static str, Token> = phf::phf_map! {
     " " => Token::NonBreakingSpace,
@@ -14,16 +19,17 @@ static COMMANDS: phf::Map<&'static str, Token> = phf::phf_map! {
     "$" => Token::Letter('$'),
     "%" => Token::Letter('%'),
     "&" => Token::OpAmpersand,
-    "," => Token::Space("0.1667"),
+    "(" => Token::InlineMathBegin,
+    ")" => Token::InlineMathEnd,
+    "," => Token::Space(THIN_SPACE_WIDTH),
     ":" => Token::Space("0.2222"),
     ";" => Token::Space("0.2778"),
     ">" => Token::Space("0.2222"),
     "\\" => Token::NewLine,
     "_" => Token::Letter('_'),
-    "AA" => Token::Letter('Å'), // TODO: valid in text mode only
-    "AE" => Token::Letter('Æ'),
     "Alpha" => Token::UprightLetter('Α'),
     "And" => Token::OpAmpersand,
+    "AxiomC" => Token::Axiom,
     "Bbbk" => Token::Letter('𝕜'),
     "Beta" => Token::UprightLetter('Β'),
     "Big" => Token::Big(Size::Scale2),
@@ -32,13 +38,14 @@ static COMMANDS: phf::Map<&'static str, Token> = phf::phf_map! {
     "Biggr" => Token::Big(Size::Scale4),
     "Bigl" => Token::Big(Size::Scale2),
     "Bigr" => Token::Big(Size::Scale2),
+    "BinaryInfC" => Token::Inference(2),
     "Box" => Token::Letter('◻'),
+    "Braket" => Token::Braket(Braket::StretchedBraket),
     "Bumpeq" => Token::Relation(ops::GEOMETRICALLY_EQUIVALENT_TO),
     "Cap" => Token::Relation(ops::DOUBLE_INTERSECTION),
     "Chi" => Token::UprightLetter('Χ'),
     "Colon" => Token::Relation(ops::PROPORTION),
     "Cup" => Token::Relation(ops::DOUBLE_UNION),
-    "DH" => Token::Letter('Ð'),
     "Dagger" => Token::Letter('‡'),
     "Delta" => Token::UprightLetter('Δ'),
     "Diamond" => Token::Letter('◊'),
@@ -49,12 +56,15 @@ static COMMANDS: phf::Map<&'static str, Token> = phf::phf_map! {
     "Finv" => Token::Letter('Ⅎ'),
     "Game" => Token::Letter('⅁'),
     "Gamma" => Token::UprightLetter('Γ'),
+    "Huge" => Token::FontSize(FontSize::Huge2),
     "Im" => Token::Letter('ℑ'),
     "Iota" => Token::UprightLetter('Ι'),
     "Join" => Token::Relation(ops::BOWTIE),
     "Kappa" => Token::UprightLetter('Κ'),
-    "L" => Token::Letter('Ł'),
+    "LARGE" => Token::FontSize(FontSize::Large3),
     "Lambda" => Token::UprightLetter('Λ'),
+    "Large" => Token::FontSize(FontSize::Large2),
+    "LeftLabel" => Token::RuleLabel(true),
     "Leftarrow" => Token::Relation(ops::LEFTWARDS_DOUBLE_ARROW),
     "Leftrightarrow" => Token::Relation(ops::LEFT_RIGHT_DOUBLE_ARROW),
     "Lleftarrow" => Token::Relation(ops::LEFTWARDS_TRIPLE_ARROW),
@@ -65,8 +75,6 @@ static COMMANDS: phf::Map<&'static str, Token> = phf::phf_map! {
     "Mu" => Token::UprightLetter('Μ'),
     "NG" => Token::Letter('Ŋ'),
     "Nu" => Token::UprightLetter('Ν'),
-    "O" => Token::Letter('Ø'),
-    "OE" => Token::Letter('Œ'),
     "Omega" => Token::UprightLetter('Ω'),
     "Omicron" => Token::UprightLetter('Ο'),
     "P" => Token::Letter('¶'),
@@ -80,16 +88,19 @@ static COMMANDS: phf::Map<&'static str, Token> = phf::phf_map! {
     }),
     "Re" => Token::Letter('ℜ'),
     "Rho" => Token::UprightLetter('Ρ'),
+    "RightLabel" => Token::RuleLabel(false),
     "Rightarrow" => Token::Relation(ops::RIGHTWARDS_DOUBLE_ARROW),
     "Rrightarrow" => Token::Relation(ops::RIGHTWARDS_TRIPLE_ARROW),
     "Rsh" => Token::Relation(ops::UPWARDS_ARROW_WITH_TIP_RIGHTWARDS),
     "S" => Token::Letter('§'),
+    "Set" => Token::Braket(Braket::StretchedSet),
     "Sigma" => Token::UprightLetter('Σ'),
     "Subset" => Token::Relation(ops::DOUBLE_SUBSET),
     "Supset" => Token::Relation(ops::DOUBLE_SUPERSET),
-    "TH" => Token::Letter('Þ'),
     "Tau" => Token::UprightLetter('Τ'),
     "Theta" => Token::UprightLetter('Θ'),
+    "TrinaryInfC" => Token::Inference(3),
+    "UnaryInfC" => Token::Inference(1),
     "Uparrow" => Token::Delimiter(ops::UPWARDS_DOUBLE_ARROW),
     "Updownarrow" => Token::Delimiter(ops::UP_DOWN_DOUBLE_ARROW),
     "Upsilon" => Token::UprightLetter('Υ'),
@@ -101,9 +112,10 @@ static COMMANDS: phf::Map<&'static str, Token> = phf::phf_map! {
     "Yright" => Token::Relation(ops::RIGHTWARDS_ARROW_TAIL),
     "Zeta" => Token::UprightLetter('Ζ'),
     "a" => Token::Letter('å'),
+    "above" => Token::Infix(Infix::Above),
     "acute" => Token::OverUnder(ops::ACUTE_ACCENT, true, None),
-    "ae" => Token::Letter('æ'),
     "aleph" => Token::Letter('ℵ'),
+    "allowbreak" => Token::CustomCmd(0, &predefined::ALLOWBREAK),
     "alpha" => Token::Letter('α'),
     "amalg" => Token::Relation(ops::AMALGAMATION_OR_COPRODUCT),
     "angle" => Token::Letter(ops::ANGLE),
@@ -118,6 +130,7 @@ static COMMANDS: phf::Map<&'static str, Token> = phf::phf_map! {
     "ast" => Token::Relation(ops::ASTERISK_OPERATOR),
     "astrosun" => Token::Letter('☉'),
     "asymp" => Token::Relation(ops::EQUIVALENT_TO),
+    "atop" => Token::Infix(Infix::Atop),
     "awint" => Token::Integral(ops::ANTICLOCKWISE_INTEGRATION),
     "backdprime" => Token::Relation(ops::REVERSED_DOUBLE_PRIME),
     "backepsilon" => Token::Relation(ops::SMALL_CONTAINS_AS_MEMBER),
@@ -179,28 +192,21 @@ static COMMANDS: phf::Map<&'static str, Token> = phf::phf_map! {
     "boxplus" => Token::Relation(ops::SQUARED_PLUS),
     "boxslash" => Token::Relation(ops::SQUARED_RISING_DIAGONAL_SLASH),
     "boxtimes" => Token::Relation(ops::SQUARED_TIMES),
+    "brace" => Token::Infix(Infix::Brace),
+    "brack" => Token::Infix(Infix::Brack),
+    "braket" => Token::Braket(Braket::Braket),
     "breve" => Token::OverUnder(ops::BREVE, true, None),
     "bullet" => Token::Relation(ops::BULLET_OPERATOR),
     "bumpeq" => Token::Relation(ops::DIFFERENCE_BETWEEN),
     "cap" => Token::Relation(ops::INTERSECTION),
     "cdot" => Token::BinaryOp(ops::MIDDLE_DOT),
-    "cdots" => Token::CustomCmd(0, &Node::Row {
-        nodes: &[
-            &Node::Operator(ops::MIDDLE_DOT.as_op(), None),
-            &Node::OperatorWithSpacing {
-                op:ops::MIDDLE_DOT.as_op(),
-                left: Some(MathSpacing::Zero),
-                right: Some(MathSpacing::Zero),
-            },
-            &Node::Operator(ops::MIDDLE_DOT.as_op(), None),
-        ],
-        style:None
-    }),
+    "cdots" => Token::CustomCmd(0, &predefined::CDOTS),
     "centerdot" => Token::Relation(ops::BULLET_OPERATOR),
     "cfrac" => Token::Frac(Some(FracAttr::CFracStyle)),
     "check" => Token::OverUnder(ops::CARON, true, Some(OpAttr::StretchyFalse)),
     "checkmark" => Token::Letter('✓'),
     "chi" => Token::Letter('χ'),
+    "choose" => Token::Infix(Infix::Choose),
     "circ" => Token::Relation(ops::RING_OPERATOR),
     "circeq" => Token::Relation(ops::RING_EQUAL_TO),
     "circlearrowleft" => Token::Relation(ops::ANTICLOCKWISE_OPEN_CIRCLE_ARROW),
@@ -211,6 +217,7 @@ static COMMANDS: phf::Map<&'static str, Token> = phf::phf_map! {
     "circledcirc" => Token::Relation(ops::CIRCLED_RING_OPERATOR),
     "circleddash" => Token::Relation(ops::CIRCLED_DASH),
     "cirfnint" => Token::Integral(ops::CIRCULATION_FUNCTION),
+    "cline" => Token::CLine,
     "clubsuit" => Token::Letter('♣'),
     "colon" => Token::Letter(':'),
     "coloneq" => Token::Relation(ops::COLON_EQUALS),
@@ -244,17 +251,19 @@ static COMMANDS: phf::Map<&'static str, Token> = phf::phf_map! {
     "dblcolon" => Token::Relation(ops::PROPORTION),
     "ddag" => Token::Letter('‡'),
     "ddagger" => Token::Letter('‡'),
+    "dddot" => Token::OverUnder(ops::COMBINING_THREE_DOTS_ABOVE, true, None),
+    "ddddot" => Token::OverUnder(ops::COMBINING_FOUR_DOTS_ABOVE, true, None),
     "ddot" => Token::OverUnder(ops::DIAERESIS, true, None),
     "ddots" => Token::Relation(ops::DOWN_RIGHT_DIAGONAL_ELLIPSIS),
     "deg" => Token::Function("deg"),
     "delta" => Token::Letter('δ'),
     "det" => Token::Function("det"),
     "dfrac" => Token::Frac(Some(FracAttr::DisplayStyleTrue)),
-    "dh" => Token::Letter('ð'),
     "diamond" => Token::Relation(ops::DIAMOND_OPERATOR),
     "diamondsuit" => Token::Letter('♢'),
     "digamma" => Token::Letter('ϝ'),
     "dim" => Token::Function("dim"),
+    "displaylimits" => Token::Limits(Limits::Display),
     "displaystyle" => Token::Style(Style::DisplayStyle),
     "div" => Token::BinaryOp(ops::DIVISION_SIGN),
     "divideontimes" => Token::Relation(ops::DIVISION_TIMES),
@@ -263,7 +272,12 @@ static COMMANDS: phf::Map<&'static str, Token> = phf::phf_map! {
     "doteq" => Token::Relation(ops::APPROACHES_THE_LIMIT),
     "doteqdot" => Token::Relation(ops::GEOMETRICALLY_EQUAL_TO),
     "dotplus" => Token::BinaryOp(ops::DOT_PLUS),
-    "dots" => Token::Relation(ops::HORIZONTAL_ELLIPSIS),
+    "dots" => Token::Dots,
+    "dotsb" => Token::CustomCmd(0, &predefined::CDOTS),
+    "dotsc" => Token::Relation(ops::HORIZONTAL_ELLIPSIS),
+    "dotsi" => Token::CustomCmd(0, &predefined::DOTSI),
+    "dotsm" => Token::CustomCmd(0, &predefined::CDOTS),
+    "dotso" => Token::Relation(ops::HORIZONTAL_ELLIPSIS),
     "dotsminusdots" => Token::Relation(ops::GEOMETRIC_PROPORTION),
     "doublebarwedge" => Token::Relation(ops::LOGICAL_AND_WITH_DOUBLE_OVERBAR),
     "downarrow" => Token::Delimiter(ops::DOWNWARDS_ARROW),
@@ -273,6 +287,7 @@ static COMMANDS: phf::Map<&'static str, Token> = phf::phf_map! {
     "dprime" => Token::Relation(ops::DOUBLE_PRIME),
     "earth" => Token::Letter('♁'),
     "ell" => Token::Letter('ℓ'),
+    "emph" => Token::Text(FontChange::Emphasis),
     "empty" => Token::Function("∅︀"), // these are two unicode characters
     "emptyset" => Token::Function("∅︀"), // these are two unicode characters
     "end" => Token::End,
@@ -296,6 +311,7 @@ static COMMANDS: phf::Map<&'static str, Token> = phf::phf_map! {
     "fcmp" => Token::Relation(ops::Z_NOTATION_SCHEMA_COMPOSITION),
     "fint" => Token::Integral(ops::INTEGRAL_AVERAGE_WITH_SLASH),
     "flat" => Token::Letter('♭'),
+    "footnotesize" => Token::FontSize(FontSize::FootnoteSize),
     "forall" => Token::Relation(ops::FOR_ALL),
     "frac" => Token::Frac(None),
     "frown" => Token::Relation(ops::FROWN),
@@ -318,11 +334,15 @@ static COMMANDS: phf::Map<&'static str, Token> = phf::phf_map! {
     "gtrsim" => Token::Relation(ops::GREATER_THAN_OR_EQUIVALENT_TO),
     "hat" => Token::OverUnder(ops::CIRCUMFLEX_ACCENT, true, Some(OpAttr::StretchyFalse)),
     "hbar" => Token::Letter('ℏ'),
+    "hbox" => Token::Text(FontChange::Inherit),
+    "hdotsfor" => Token::HDotsFor,
     "heartsuit" => Token::Letter('♡'),
+    "hline" => Token::HLine,
     "hom" => Token::Function("hom"),
     "hookleftarrow" => Token::Relation(ops::LEFTWARDS_ARROW_WITH_HOOK),
     "hookrightarrow" => Token::Relation(ops::RIGHTWARDS_ARROW_WITH_HOOK),
     "hslash" => Token::Letter('ℏ'),
+    "huge" => Token::FontSize(FontSize::Huge),
     "iff" => Token::Relation(ops::LONG_LEFT_RIGHT_DOUBLE_ARROW),
     "iiiint" => Token::Integral(ops::QUADRUPLE_INTEGRAL_OPERATOR),
     "iiint" => Token::Integral(ops::TRIPLE_INTEGRAL),
@@ -333,6 +353,7 @@ static COMMANDS: phf::Map<&'static str, Token> = phf::phf_map! {
     "in" => Token::Relation(ops::ELEMENT_OF),
     "inf" => Token::Lim("inf"),
     "infty" => Token::Letter(ops::INFINITY),
+    "injlim" => Token::Lim("inj lim"),
     "int" => Token::Integral(ops::INTEGRAL),
     "intBar" => Token::Integral(ops::INTEGRAL_WITH_DOUBLE_STROKE),
     "intbar" => Token::Integral(ops::FINITE_PARTL_INTEGRAL),
@@ -344,12 +365,12 @@ static COMMANDS: phf::Map<&'static str, Token> = phf::phf_map! {
     "kappa" => Token::Letter('κ'),
     "ker" => Token::Function("ker"),
     "kernelcontraction" => Token::Relation(ops::HOMOTHETIC),
-    "l" => Token::Letter('ł'),
     "lBrace" => Token::Delimiter(ops::LEFT_WHITE_CURLY_BRACKET),
     "lVert" => Token::Delimiter(ops::DOUBLE_VERTICAL_LINE),
     "lambda" => Token::Letter('λ'),
     "land" => Token::Relation(ops::LOGICAL_AND),
     "langle" => Token::Delimiter(ops::MATHEMATICAL_LEFT_ANGLE_BRACKET),
+    "large" => Token::FontSize(FontSize::Large),
     "lbrace" => Token::Delimiter(ops::LEFT_CURLY_BRACKET),
     "lbrack" => Token::Delimiter(ops::LEFT_SQUARE_BRACKET),
     "lceil" => Token::Delimiter(ops::LEFT_CEILING),
@@ -365,6 +386,7 @@ static COMMANDS: phf::Map<&'static str, Token> = phf::phf_map! {
     "leftrightarrows" => Token::Relation(ops::LEFTWARDS_ARROW_OVER_RIGHTWARDS_ARROW),
     "leftrightharpoons" => Token::Relation(ops::LEFTWARDS_HARPOON_OVER_RIGHTWARDS_HARPOON),
     "leftrightsquigarrow" => Token::Relation(ops::LEFT_RIGHT_WAVE_ARROW),
+    "leftroot" => Token::RootShift(false),
     "leftthreetimes" => Token::Relation(ops::LEFT_SEMIDIRECT_PRODUCT),
     "leq" => Token::Relation(ops::LESS_THAN_OR_EQUAL_TO),
     "leqq" => Token::Relation(ops::LESS_THAN_OVER_EQUAL_TO),
@@ -382,7 +404,7 @@ static COMMANDS: phf::Map<&'static str, Token> = phf::phf_map! {
     "lightning" => Token::Relation(ops::DOWNWARDS_ZIGZAG_ARROW),
     "lim" => Token::Lim("lim"),
     "liminf" => Token::Lim("lim inf"),
-    "limits" => Token::Limits,
+    "limits" => Token::Limits(Limits::Always),
     "limsup" => Token::Lim("lim sup"),
     "ll" => Token::Relation(ops::MUCH_LESS_THAN),
     "llangle" => Token::Delimiter(ops::Z_NOTATION_LEFT_BINDING_BRACKET),
@@ -412,15 +434,34 @@ static COMMANDS: phf::Map<&'static str, Token> = phf::phf_map! {
     "mars" => Token::Letter('♂'),
     "mathbb" => Token::Transform(MathVariant::Transform(TextTransform::DoubleStruck)),
     "mathbf" => Token::Transform(MathVariant::Transform(TextTransform::Bold)),
-    "mathcal" => Token::Transform(MathVariant::Transform(TextTransform::Script)),
+    "mathbfcal" => Token::Transform(MathVariant::Transform(TextTransform::BoldScriptChancery)),
+    "mathbffrak" => Token::Transform(MathVariant::Transform(TextTransform::BoldFraktur)),
+    "mathbfit" => Token::Transform(MathVariant::Transform(TextTransform::ExplicitBoldItalic)),
+    "mathbfscr" => Token::Transform(MathVariant::Transform(TextTransform::BoldScriptRoundhand)),
+    "mathbfsf" => Token::Transform(MathVariant::Transform(TextTransform::BoldSansSerif)),
+    "mathbfsfit" => Token::Transform(MathVariant::Transform(TextTransform::SansSerifBoldItalic)),
+    "mathbfup" => Token::Transform(MathVariant::Transform(TextTransform::Bold)),
+    "mathcal" => Token::Transform(MathVariant::Transform(TextTransform::ScriptChancery)),
     "mathfrak" => Token::Transform(MathVariant::Transform(TextTransform::Fraktur)),
+    "mathinitial" => Token::Transform(MathVariant::Transform(TextTransform::Initial)),
     "mathit" => Token::Transform(MathVariant::Transform(TextTransform::Italic)),
+    "mathlooped" => Token::Transform(MathVariant::Transform(TextTransform::Looped)),
+    "mathnormal" => Token::Transform(MathVariant::Reset),
+    "mathring" => Token::OverUnder(ops::RING_ABOVE, true, None),
     "mathrm" => Token::Transform(MathVariant::Normal),
-    "mathscr" => Token::Transform(MathVariant::Transform(TextTransform::Script)),
+    "mathscr" => Token::Transform(MathVariant::Transform(TextTransform::ScriptRoundhand)),
     "mathsf" => Token::Transform(MathVariant::Transform(TextTransform::SansSerif)),
+    "mathsfbf" => Token::Transform(MathVariant::Transform(TextTransform::BoldSansSerif)),
+    "mathsfbfit" => Token::Transform(MathVariant::Transform(TextTransform::SansSerifBoldItalic)),
+    "mathsfit" => Token::Transform(MathVariant::Transform(TextTransform::SansSerifItalic)),
+    "mathsfup" => Token::Transform(MathVariant::Transform(TextTransform::SansSerif)),
+    "mathstretched" => Token::Transform(MathVariant::Transform(TextTransform::Stretched)),
     "mathstrut" => Token::HardcodedMathML(r#"<mpadded width="0" style="visibility:hidden"><mo stretchy="false">(</mo></mpadded>"#),
+    "mathtailed" => Token::Transform(MathVariant::Transform(TextTransform::Tailed)),
     "mathtt" => Token::Transform(MathVariant::Transform(TextTransform::Monospace)),
+    "mathup" => Token::Transform(MathVariant::Normal),
     "max" => Token::Lim("max"),
+    "mbox" => Token::Text(FontChange::Inherit),
     "measeq" => Token::Relation(ops::MEASURED_BY), // from "stix"
     "measuredangle" => Token::Letter(ops::MEASURED_ANGLE),
     "mercury" => Token::Letter('☿'),
@@ -432,6 +473,7 @@ static COMMANDS: phf::Map<&'static str, Token> = phf::phf_map! {
     "models" => Token::Relation(ops::TRUE),
     "mp" => Token::BinaryOp(ops::MINUS_OR_PLUS_SIGN),
     "mu" => Token::Letter('μ'),
+    "multicolumn" => Token::MultiColumn,
     "multimap" => Token::Relation(ops::MULTIMAP),
     "nLeftarrow" => Token::Relation(ops::LEFTWARDS_DOUBLE_ARROW_WITH_STROKE),
     "nLeftrightarrow" => Token::Relation(ops::LEFT_RIGHT_DOUBLE_ARROW_WITH_STROKE),
@@ -444,6 +486,7 @@ static COMMANDS: phf::Map<&'static str, Token> = phf::phf_map! {
     "neptune" => Token::Letter('♆'),
     "neq" => Token::Relation(ops::NOT_EQUAL_TO),
     "nequiv" => Token::Relation(ops::NOT_IDENTICAL_TO),
+    "newline" => Token::NewLine,
     "nexists" => Token::Relation(ops::THERE_DOES_NOT_EXIST),
     "ng" => Token::Letter('ŋ'),
     "ngeq" => Token::Relation(ops::NEITHER_GREATER_THAN_NOR_EQUAL_TO),
@@ -458,6 +501,8 @@ static COMMANDS: phf::Map<&'static str, Token> = phf::phf_map! {
     "nlessgt" => Token::Relation(ops::NEITHER_LESS_THAN_NOR_GREATER_THAN),
     "nlesssim" => Token::Relation(ops::NEITHER_LESS_THAN_NOR_EQUIVALENT_TO),
     "nmid" => Token::Relation(ops::DOES_NOT_DIVIDE),
+    "nolimits" => Token::Limits(Limits::Never),
+    "normalsize" => Token::FontSize(FontSize::NormalSize),
     "not" => Token::Not,
     "notin" => Token::Relation(ops::NOT_AN_ELEMENT_OF),
     "nparallel" => Token::Relation(ops::NOT_PARALLEL_TO),
@@ -477,10 +522,8 @@ static COMMANDS: phf::Map<&'static str, Token> = phf::phf_map! {
     "nvDash" => Token::Relation(ops::NOT_TRUE),
     "nvdash" => Token::Relation(ops::DOES_NOT_PROVE),
     "nwarrow" => Token::Relation(ops::NORTH_WEST_ARROW),
-    "o" => Token::Letter('ø'),
     "odot" => Token::Relation(ops::CIRCLED_DOT_OPERATOR),
     "odv" => Token::CustomCmd(2, &predefined::ODV),
-    "oe" => Token::Letter('œ'),
     "oiiint" => Token::Integral(ops::VOLUME_INTEGRAL),
     "oiint" => Token::Integral(ops::SURFACE_INTEGRAL),
     "oint" => Token::Integral(ops::CONTOUR_INTEGRAL),
@@ -492,6 +535,7 @@ static COMMANDS: phf::Map<&'static str, Token> = phf::phf_map! {
     "oplus" => Token::Relation(ops::CIRCLED_PLUS),
     "oslash" => Token::Relation(ops::CIRCLED_DIVISION_SLASH),
     "otimes" => Token::Relation(ops::CIRCLED_TIMES),
+    "over" => Token::Infix(Infix::Over),
     "overbrace" => Token::OverUnderBrace(ops::TOP_CURLY_BRACKET, true),
     "overbracket" => Token::OverUnderBrace(ops::TOP_SQUARE_BRACKET, true),
     "overleftarrow" => Token::OverUnder(ops::LEFTWARDS_ARROW.as_op(), true, None),
@@ -518,6 +562,7 @@ static COMMANDS: phf::Map<&'static str, Token> = phf::phf_map! {
     "precsim" => Token::Relation(ops::PRECEDES_OR_EQUIVALENT_TO),
     "prime" => Token::Relation(ops::PRIME),
     "prod" => Token::BigOp(ops::N_ARY_PRODUCT),
+    "projlim" => Token::Lim("proj lim"),
     "propto" => Token::Relation(ops::PROPORTIONAL_TO),
     "psi" => Token::Letter('ψ'),
     "qprime" => Token::Relation(ops::QUADRUPLE_PRIME),
@@ -554,9 +599,11 @@ static COMMANDS: phf::Map<&'static str, Token> = phf::phf_map! {
     "rvert" => Token::Delimiter(ops::VERTICAL_LINE),
     "saturn" => Token::Letter('♄'),
     "scriptscriptstyle" => Token::Style(Style::ScriptScriptStyle),
+    "scriptsize" => Token::FontSize(FontSize::ScriptSize),
     "scriptstyle" => Token::Style(Style::ScriptStyle),
     "searrow" => Token::Relation(ops::SOUTH_EAST_ARROW),
     "sec" => Token::Function("sec"),
+    "set" => Token::Braket(Braket::Set),
     "setminus" => Token::Relation(ops::SET_MINUS),
     "sgn" => Token::Function("sgn"),
     "sharp" => Token::Letter('♯'),
@@ -566,6 +613,7 @@ static COMMANDS: phf::Map<&'static str, Token> = phf::phf_map! {
     "sin" => Token::Function("sin"),
     "sinh" => Token::Function("sinh"),
     "slashed" => Token::Slashed,
+    "small" => Token::FontSize(FontSize::Small),
     "smallsetminus" => Token::Relation(ops::SMALL_REVERSE_SOLIDUS),
     "smile" => Token::Relation(ops::SMILE),
     "spadesuit" => Token::Letter('♠'),
@@ -578,7 +626,6 @@ static COMMANDS: phf::Map<&'static str, Token> = phf::phf_map! {
     "sqsupset" => Token::Relation(ops::SQUARE_ORIGINAL_OF),
     "sqsupseteq" => Token::Relation(ops::SQUARE_ORIGINAL_OF_OR_EQUAL_TO),
     "square" => Token::Letter('□'),
-    "ss" => Token::Letter('ß'),
     "stackrel" => Token::Overset,
     "star" => Token::Relation(ops::STAR_OPERATOR),
     "stareq" => Token::Relation(ops::STAR_EQUALS), // from "stix"
@@ -602,24 +649,50 @@ static COMMANDS: phf::Map<&'static str, Token> = phf::phf_map! {
     "supseteq" => Token::Relation(ops::SUPERSET_OF_OR_EQUAL_TO),
     "supsetneq" => Token::Relation(ops::SUPERSET_OF_WITH_NOT_EQUAL_TO),
     "supsetneqq" => Token::Relation(ops::SUPERSET_OF_ABOVE_NOT_EQUAL_TO),
+    "surd" => Token::Letter(ops::SQUARE_ROOT),
     "swarrow" => Token::Relation(ops::SOUTH_WEST_ARROW),
-    "symbf" => Token::Transform(MathVariant::Transform(TextTransform::BoldItalic)),
+    "symbb" => Token::Transform(MathVariant::Transform(TextTransform::DoubleStruck)),
+    "symbf" => Token::Transform(MathVariant::Transform(TextTransform::BoldUprightLatin)),
+    "symbfcal" => Token::Transform(MathVariant::Transform(TextTransform::BoldScriptChancery)),
+    "symbffrak" => Token::Transform(MathVariant::Transform(TextTransform::BoldFraktur)),
+    "symbfit" => Token::Transform(MathVariant::Transform(TextTransform::ExplicitBoldItalic)),
+    "symbfscr" => Token::Transform(MathVariant::Transform(TextTransform::BoldScriptRoundhand)),
+    "symbfsf" => Token::Transform(MathVariant::Transform(TextTransform::SansSerifBoldUprightLatin)),
+    "symbfsfit" => Token::Transform(MathVariant::Transform(TextTransform::SansSerifBoldItalic)),
+    "symbfsfup" => Token::Transform(MathVariant::Transform(TextTransform::BoldSansSerif)),
+    "symbfup" => Token::Transform(MathVariant::Transform(TextTransform::Bold)),
+    "symcal" => Token::Transform(MathVariant::Transform(TextTransform::ScriptChancery)),
+    "symfrak" => Token::Transform(MathVariant::Transform(TextTransform::Fraktur)),
+    "symit" => Token::Transform(MathVariant::Transform(TextTransform::Italic)),
+    "symnormal" => Token::Transform(MathVariant::Reset),
+    "symrm" => Token::Transform(MathVariant::Normal),
+    "symscr" => Token::Transform(MathVariant::Transform(TextTransform::ScriptRoundhand)),
+    "symsf" => Token::Transform(MathVariant::Transform(TextTransform::SansSerif)),
+    "symsfit" => Token::Transform(MathVariant::Transform(TextTransform::SansSerifItalic)),
+    "symsfup" => Token::Transform(MathVariant::Transform(TextTransform::SansSerif)),
+    "symtt" => Token::Transform(MathVariant::Transform(TextTransform::Monospace)),
+    "symup" => Token::Transform(MathVariant::Normal),
     "tan" => Token::Function("tan"),
     "tanh" => Token::Function("tanh"),
     "tau" => Token::Letter('τ'),
     "tbinom" => Token::Binom(Some(FracAttr::DisplayStyleFalse)),
-    "text" => Token::Text(None),
-    "textbf" => Token::Text(Some(TextTransform::Bold)),
-    "textit" => Token::Text(Some(TextTransform::Italic)),
+    "text" => Token::Text(FontChange::Inherit),
+    "textbf" => Token::Text(FontChange::Bold),
+    "textit" => Token::Text(FontChange::Italic),
+    "textnormal" => Token::Text(FontChange::Normal),
+    "textrm" => Token::Text(FontChange::Roman),
+    "textsf" => Token::Text(FontChange::SansSerif),
+    // There is no slanted alphabet in Unicode.
+    "textsl" => Token::Text(FontChange::Italic),
     "textstyle" => Token::Style(Style::TextStyle),
-    "texttt" => Token::Text(Some(TextTransform::Monospace)),
-    "textyen" => Token::Letter('¥'),
+    "texttt" => Token::Text(FontChange::Monospace),
+    "textup" => Token::Text(FontChange::Upright),
     "tfrac" => Token::Frac(Some(FracAttr::DisplayStyleFalse)),
-    "th" => Token::Letter('þ'),
     "therefore" => Token::Relation(ops::THEREFORE),
     "theta" => Token::Letter('θ'),
     "tilde" => Token::OverUnder(ops::TILDE, true, Some(OpAttr::StretchyFalse)),
     "times" => Token::BinaryOp(ops::MULTIPLICATION_SIGN),
+    "tiny" => Token::FontSize(FontSize::Tiny),
     "to" => Token::Relation(ops::RIGHTWARDS_ARROW),
     "top" => Token::Letter(ops::DOWN_TACK),
     "triangle" => Token::Letter('△'),
@@ -641,6 +714,7 @@ static COMMANDS: phf::Map<&'static str, Token> = phf::phf_map! {
     "upharpoonleft" => Token::Relation(ops::UPWARDS_HARPOON_WITH_BARB_LEFTWARDS),
     "upharpoonright" => Token::Relation(ops::UPWARDS_HARPOON_WITH_BARB_RIGHTWARDS),
     "uplus" => Token::Relation(ops::MULTISET_UNION),
+    "uproot" => Token::RootShift(true),
     "upsilon" => Token::Letter('υ'),
     "upuparrows" => Token::Relation(ops::UPWARDS_PAIRED_ARROWS),
     "uranus" => Token::Letter('♅'),
@@ -658,11 +732,15 @@ static COMMANDS: phf::Map<&'static str, Token> = phf::phf_map! {
     "varUpsilon" => Token::Letter('ϒ'), // italicized
     "varXi" => Token::Letter('Ξ'), // italicized
     "varepsilon" => Token::Letter('ε'),
+    "varinjlim" => Token::VarLim(ops::RIGHTWARDS_ARROW.as_op(), false),
     "varkappa" => Token::Letter('ϰ'),
+    "varliminf" => Token::VarLim(ops::LOW_LINE, false),
+    "varlimsup" => Token::VarLim(ops::OVERLINE.as_op(), true),
     "varnothing" => Token::Letter(ops::EMPTY_SET),
     "varointclockwise" => Token::Integral(ops::CLOCKWISE_CONTOUR_INTEGRAL),
     "varphi" => Token::Letter('φ'),
     "varpi" => Token::Letter('ϖ'),
+    "varprojlim" => Token::VarLim(ops::LEFTWARDS_ARROW.as_op(), false),
     "varrho" => Token::Letter('ϱ'),
     "varsigma" => Token::Letter('ς'),
     "vartheta" => Token::Letter('ϑ'),
@@ -690,31 +768,662 @@ static COMMANDS: phf::Map<&'static str, Token> = phf::phf_map! {
     "}" => Token::Delimiter(ops::RIGHT_CURLY_BRACKET),
 };
 
-pub fn get_command(command: &str) -> Token<'_> {
+/// Non-ASCII characters which are not letters, with the token of the command
+/// that produces the same character (e.g. `≤` is lexed like `\le`).
+static UNICODE_SYMBOLS: phf::Map<char, Token> = phf::phf_map! {
+    '¬' => Token::Relation(ops::NOT_SIGN),
+    '±' => Token::BinaryOp(ops::PLUS_MINUS_SIGN),
+    '·' => Token::BinaryOp(ops::MIDDLE_DOT),
+    '×' => Token::BinaryOp(ops::MULTIPLICATION_SIGN),
+    '÷' => Token::BinaryOp(ops::DIVISION_SIGN),
+    'Α' => Token::UprightLetter('Α'),
+    'Β' => Token::UprightLetter('Β'),
+    'Γ' => Token::UprightLetter('Γ'),
+    'Δ' => Token::UprightLetter('Δ'),
+    'Ε' => Token::UprightLetter('Ε'),
+    'Ζ' => Token::UprightLetter('Ζ'),
+    'Η' => Token::UprightLetter('Η'),
+    'Θ' => Token::UprightLetter('Θ'),
+    'Ι' => Token::UprightLetter('Ι'),
+    'Κ' => Token::UprightLetter('Κ'),
+    'Λ' => Token::UprightLetter('Λ'),
+    'Μ' => Token::UprightLetter('Μ'),
+    'Ν' => Token::UprightLetter('Ν'),
+    'Ξ' => Token::UprightLetter('Ξ'),
+    'Ο' => Token::UprightLetter('Ο'),
+    'Π' => Token::UprightLetter('Π'),
+    'Ρ' => Token::UprightLetter('Ρ'),
+    'Σ' => Token::UprightLetter('Σ'),
+    'Τ' => Token::UprightLetter('Τ'),
+    'Υ' => Token::UprightLetter('Υ'),
+    'Φ' => Token::UprightLetter('Φ'),
+    'Χ' => Token::UprightLetter('Χ'),
+    'Ψ' => Token::UprightLetter('Ψ'),
+    'Ω' => Token::UprightLetter('Ω'),
+    '‖' => Token::Delimiter(ops::DOUBLE_VERTICAL_LINE),
+    '…' => Token::Relation(ops::HORIZONTAL_ELLIPSIS),
+    '‵' => Token::Relation(ops::REVERSED_PRIME),
+    '‶' => Token::Relation(ops::REVERSED_DOUBLE_PRIME),
+    '‷' => Token::Relation(ops::REVERSED_TRIPLE_PRIME),
+    '←' => Token::Relation(ops::LEFTWARDS_ARROW),
+    '↑' => Token::Delimiter(ops::UPWARDS_ARROW),
+    '→' => Token::Relation(ops::RIGHTWARDS_ARROW),
+    '↓' => Token::Delimiter(ops::DOWNWARDS_ARROW),
+    '↔' => Token::Relation(ops::LEFT_RIGHT_ARROW),
+    '↕' => Token::Delimiter(ops::UP_DOWN_ARROW),
+    '↖' => Token::Relation(ops::NORTH_WEST_ARROW),
+    '↗' => Token::Relation(ops::NORTH_EAST_ARROW),
+    '↘' => Token::Relation(ops::SOUTH_EAST_ARROW),
+    '↙' => Token::Relation(ops::SOUTH_WEST_ARROW),
+    '↚' => Token::Relation(ops::LEFTWARDS_ARROW_WITH_STROKE),
+    '↛' => Token::Relation(ops::RIGHTWARDS_ARROW_WITH_STROKE),
+    '↢' => Token::Relation(ops::LEFTWARDS_ARROW_WITH_TAIL),
+    '↣' => Token::Relation(ops::RIGHTWARDS_ARROW_WITH_TAIL),
+    '↦' => Token::Relation(ops::RIGHTWARDS_ARROW_FROM_BAR),
+    '↩' => Token::Relation(ops::LEFTWARDS_ARROW_WITH_HOOK),
+    '↪' => Token::Relation(ops::RIGHTWARDS_ARROW_WITH_HOOK),
+    '↫' => Token::Relation(ops::LEFTWARDS_ARROW_WITH_LOOP),
+    '↬' => Token::Relation(ops::RIGHTWARDS_ARROW_WITH_LOOP),
+    '↭' => Token::Relation(ops::LEFT_RIGHT_WAVE_ARROW),
+    '↮' => Token::Relation(ops::LEFT_RIGHT_ARROW_WITH_STROKE),
+    '↯' => Token::Relation(ops::DOWNWARDS_ZIGZAG_ARROW),
+    '↰' => Token::Relation(ops::UPWARDS_ARROW_WITH_TIP_LEFTWARDS),
+    '↱' => Token::Relation(ops::UPWARDS_ARROW_WITH_TIP_RIGHTWARDS),
+    '↶' => Token::Relation(ops::ANTICLOCKWISE_TOP_SEMICIRCLE_ARROW),
+    '↷' => Token::Relation(ops::CLOCKWISE_TOP_SEMICIRCLE_ARROW),
+    '↺' => Token::Relation(ops::ANTICLOCKWISE_OPEN_CIRCLE_ARROW),
+    '↻' => Token::Relation(ops::CLOCKWISE_OPEN_CIRCLE_ARROW),
+    '↼' => Token::Relation(ops::LEFTWARDS_HARPOON_WITH_BARB_UPWARDS),
+    '↽' => Token::Relation(ops::LEFTWARDS_HARPOON_WITH_BARB_DOWNWARDS),
+    '↾' => Token::Relation(ops::UPWARDS_HARPOON_WITH_BARB_RIGHTWARDS),
+    '↿' => Token::Relation(ops::UPWARDS_HARPOON_WITH_BARB_LEFTWARDS),
+    '⇀' => Token::Relation(ops::RIGHTWARDS_HARPOON_WITH_BARB_UPWARDS),
+    '⇁' => Token::Relation(ops::RIGHTWARDS_HARPOON_WITH_BARB_DOWNWARDS),
+    '⇂' => Token::Relation(ops::DOWNWARDS_HARPOON_WITH_BARB_RIGHTWARDS),
+    '⇃' => Token::Relation(ops::DOWNWARDS_HARPOON_WITH_BARB_LEFTWARDS),
+    '⇄' => Token::Relation(ops::RIGHTWARDS_ARROW_OVER_LEFTWARDS_ARROW),
+    '⇆' => Token::Relation(ops::LEFTWARDS_ARROW_OVER_RIGHTWARDS_ARROW),
+    '⇇' => Token::Relation(ops::LEFTWARDS_PAIRED_ARROWS),
+    '⇈' => Token::Relation(ops::UPWARDS_PAIRED_ARROWS),
+    '⇉' => Token::Relation(ops::RIGHTWARDS_PAIRED_ARROWS),
+    '⇊' => Token::Relation(ops::DOWNWARDS_PAIRED_ARROWS),
+    '⇋' => Token::Relation(ops::LEFTWARDS_HARPOON_OVER_RIGHTWARDS_HARPOON),
+    '⇌' => Token::Relation(ops::RIGHTWARDS_HARPOON_OVER_LEFTWARDS_HARPOON),
+    '⇍' => Token::Relation(ops::LEFTWARDS_DOUBLE_ARROW_WITH_STROKE),
+    '⇎' => Token::Relation(ops::LEFT_RIGHT_DOUBLE_ARROW_WITH_STROKE),
+    '⇏' => Token::Relation(ops::RIGHTWARDS_DOUBLE_ARROW_WITH_STROKE),
+    '⇐' => Token::Relation(ops::LEFTWARDS_DOUBLE_ARROW),
+    '⇑' => Token::Delimiter(ops::UPWARDS_DOUBLE_ARROW),
+    '⇒' => Token::Relation(ops::RIGHTWARDS_DOUBLE_ARROW),
+    '⇓' => Token::Delimiter(ops::DOWNWARDS_DOUBLE_ARROW),
+    '⇔' => Token::Relation(ops::LEFT_RIGHT_DOUBLE_ARROW),
+    '⇕' => Token::Delimiter(ops::UP_DOWN_DOUBLE_ARROW),
+    '⇚' => Token::Relation(ops::LEFTWARDS_TRIPLE_ARROW),
+    '⇛' => Token::Relation(ops::RIGHTWARDS_TRIPLE_ARROW),
+    '⇝' => Token::Relation(ops::RIGHTWARDS_SQUIGGLE_ARROW),
+    '∀' => Token::Relation(ops::FOR_ALL),
+    '∃' => Token::Relation(ops::THERE_EXISTS),
+    '∄' => Token::Relation(ops::THERE_DOES_NOT_EXIST),
+    '∇' => Token::UprightLetter(ops::NABLA),
+    '∈' => Token::Relation(ops::ELEMENT_OF),
+    '∉' => Token::Relation(ops::NOT_AN_ELEMENT_OF),
+    '∋' => Token::Relation(ops::CONTAINS_AS_MEMBER),
+    '∍' => Token::Relation(ops::SMALL_CONTAINS_AS_MEMBER),
+    '∏' => Token::BigOp(ops::N_ARY_PRODUCT),
+    '∐' => Token::BigOp(ops::N_ARY_COPRODUCT),
+    '∑' => Token::BigOp(ops::N_ARY_SUMMATION),
+    '∓' => Token::BinaryOp(ops::MINUS_OR_PLUS_SIGN),
+    '∔' => Token::BinaryOp(ops::DOT_PLUS),
+    '∖' => Token::Relation(ops::SET_MINUS),
+    '∗' => Token::Relation(ops::ASTERISK_OPERATOR),
+    '∘' => Token::Relation(ops::RING_OPERATOR),
+    '∙' => Token::Relation(ops::BULLET_OPERATOR),
+    '∝' => Token::Relation(ops::PROPORTIONAL_TO),
+    '∣' => Token::Relation(ops::DIVIDES),
+    '∤' => Token::Relation(ops::DOES_NOT_DIVIDE),
+    '∥' => Token::Relation(ops::PARALLEL_TO),
+    '∦' => Token::Relation(ops::NOT_PARALLEL_TO),
+    '∧' => Token::Relation(ops::LOGICAL_AND),
+    '∨' => Token::Relation(ops::LOGICAL_OR),
+    '∩' => Token::Relation(ops::INTERSECTION),
+    '∪' => Token::Relation(ops::UNION),
+    '∫' => Token::Integral(ops::INTEGRAL),
+    '∬' => Token::Integral(ops::DOUBLE_INTEGRAL),
+    '∭' => Token::Integral(ops::TRIPLE_INTEGRAL),
+    '∮' => Token::Integral(ops::CONTOUR_INTEGRAL),
+    '∯' => Token::Integral(ops::SURFACE_INTEGRAL),
+    '∰' => Token::Integral(ops::VOLUME_INTEGRAL),
+    '∱' => Token::Integral(ops::CLOCKWISE_INTEGRAL),
+    '∲' => Token::Integral(ops::CLOCKWISE_CONTOUR_INTEGRAL),
+    '∳' => Token::Integral(ops::ANTICLOCKWISE_CONTOUR_INTEGRAL),
+    '∴' => Token::Relation(ops::THEREFORE),
+    '∵' => Token::Relation(ops::BECAUSE),
+    '∷' => Token::Relation(ops::PROPORTION),
+    '∹' => Token::Relation(ops::EXCESS),
+    '∺' => Token::Relation(ops::GEOMETRIC_PROPORTION),
+    '∻' => Token::Relation(ops::HOMOTHETIC),
+    '∼' => Token::Relation(ops::TILDE_OPERATOR),
+    '∽' => Token::Relation(ops::REVERSED_TILDE),
+    '≀' => Token::Relation(ops::WREATH_PRODUCT),
+    '≁' => Token::Relation(ops::NOT_TILDE),
+    '≂' => Token::Relation(ops::MINUS_TILDE),
+    '≃' => Token::Relation(ops::ASYMPTOTICALLY_EQUAL_TO),
+    '≅' => Token::Relation(ops::APPROXIMATELY_EQUAL_TO),
+    '≈' => Token::Relation(ops::ALMOST_EQUAL_TO),
+    '≊' => Token::Relation(ops::ALMOST_EQUAL_OR_EQUAL_TO),
+    '≍' => Token::Relation(ops::EQUIVALENT_TO),
+    '≎' => Token::Relation(ops::GEOMETRICALLY_EQUIVALENT_TO),
+    '≏' => Token::Relation(ops::DIFFERENCE_BETWEEN),
+    '≐' => Token::Relation(ops::APPROACHES_THE_LIMIT),
+    '≑' => Token::Relation(ops::GEOMETRICALLY_EQUAL_TO),
+    '≒' => Token::Relation(ops::APPROXIMATELY_EQUAL_TO_OR_THE_IMAGE_OF),
+    '≓' => Token::Relation(ops::IMAGE_OF_OR_APPROXIMATELY_EQUAL_TO),
+    '≔' => Token::Relation(ops::COLON_EQUALS),
+    '≕' => Token::Relation(ops::EQUALS_COLON),
+    '≖' => Token::Relation(ops::RING_IN_EQUAL_TO),
+    '≗' => Token::Relation(ops::RING_EQUAL_TO),
+    '≘' => Token::Relation(ops::CORRESPONDS_TO),
+    '≙' => Token::Relation(ops::ESTIMATES),
+    '≚' => Token::Relation(ops::EQUIANGULAR_TO),
+    '≛' => Token::Relation(ops::STAR_EQUALS),
+    '≜' => Token::Relation(ops::DELTA_EQUAL_TO),
+    '≝' => Token::Relation(ops::EQUAL_TO_BY_DEFINITION),
+    '≞' => Token::Relation(ops::MEASURED_BY),
+    '≟' => Token::Relation(ops::QUESTIONED_EQUAL_TO),
+    '≠' => Token::Relation(ops::NOT_EQUAL_TO),
+    '≡' => Token::Relation(ops::IDENTICAL_TO),
+    '≢' => Token::Relation(ops::NOT_IDENTICAL_TO),
+    '≤' => Token::Relation(ops::LESS_THAN_OR_EQUAL_TO),
+    '≥' => Token::Relation(ops::GREATER_THAN_OR_EQUAL_TO),
+    '≦' => Token::Relation(ops::LESS_THAN_OVER_EQUAL_TO),
+    '≧' => Token::Relation(ops::GREATER_THAN_OVER_EQUAL_TO),
+    '≨' => Token::Relation(ops::LESS_THAN_BUT_NOT_EQUAL_TO),
+    '≩' => Token::Relation(ops::GREATER_THAN_BUT_NOT_EQUAL_TO),
+    '≪' => Token::Relation(ops::MUCH_LESS_THAN),
+    '≫' => Token::Relation(ops::MUCH_GREATER_THAN),
+    '≬' => Token::Relation(ops::BETWEEN),
+    '≮' => Token::Relation(ops::NOT_LESS_THAN),
+    '≯' => Token::Relation(ops::NOT_GREATER_THAN),
+    '≰' => Token::Relation(ops::NEITHER_LESS_THAN_NOR_EQUAL_TO),
+    '≱' => Token::Relation(ops::NEITHER_GREATER_THAN_NOR_EQUAL_TO),
+    '≲' => Token::Relation(ops::LESS_THAN_OR_EQUIVALENT_TO),
+    '≳' => Token::Relation(ops::GREATER_THAN_OR_EQUIVALENT_TO),
+    '≴' => Token::Relation(ops::NEITHER_LESS_THAN_NOR_EQUIVALENT_TO),
+    '≵' => Token::Relation(ops::NEITHER_GREATER_THAN_NOR_EQUIVALENT_TO),
+    '≶' => Token::Relation(ops::LESS_THAN_OR_GREATER_THAN),
+    '≷' => Token::Relation(ops::GREATER_THAN_OR_LESS_THAN),
+    '≸' => Token::Relation(ops::NEITHER_LESS_THAN_NOR_GREATER_THAN),
+    '≹' => Token::Relation(ops::NEITHER_GREATER_THAN_NOR_LESS_THAN),
+    '≺' => Token::Relation(ops::PRECEDES),
+    '≻' => Token::Relation(ops::SUCCEEDS),
+    '≼' => Token::Relation(ops::PRECEDES_OR_EQUAL_TO),
+    '≽' => Token::Relation(ops::SUCCEEDS_OR_EQUAL_TO),
+    '≾' => Token::Relation(ops::PRECEDES_OR_EQUIVALENT_TO),
+    '≿' => Token::Relation(ops::SUCCEEDS_OR_EQUIVALENT_TO),
+    '⊀' => Token::Relation(ops::DOES_NOT_PRECEDE),
+    '⊁' => Token::Relation(ops::DOES_NOT_SUCCEED),
+    '⊂' => Token::Relation(ops::SUBSET_OF),
+    '⊃' => Token::Relation(ops::SUPERSET_OF),
+    '⊄' => Token::Relation(ops::NOT_A_SUBSET_OF),
+    '⊅' => Token::Relation(ops::NOT_A_SUPERSET_OF),
+    '⊆' => Token::Relation(ops::SUBSET_OF_OR_EQUAL_TO),
+    '⊇' => Token::Relation(ops::SUPERSET_OF_OR_EQUAL_TO),
+    '⊈' => Token::Relation(ops::NEITHER_A_SUBSET_OF_NOR_EQUAL_TO),
+    '⊉' => Token::Relation(ops::NEITHER_A_SUPERSET_OF_NOR_EQUAL_TO),
+    '⊊' => Token::Relation(ops::SUBSET_OF_WITH_NOT_EQUAL_TO),
+    '⊋' => Token::Relation(ops::SUPERSET_OF_WITH_NOT_EQUAL_TO),
+    '⊎' => Token::Relation(ops::MULTISET_UNION),
+    '⊏' => Token::Relation(ops::SQUARE_IMAGE_OF),
+    '⊐' => Token::Relation(ops::SQUARE_ORIGINAL_OF),
+    '⊑' => Token::Relation(ops::SQUARE_IMAGE_OF_OR_EQUAL_TO),
+    '⊒' => Token::Relation(ops::SQUARE_ORIGINAL_OF_OR_EQUAL_TO),
+    '⊓' => Token::Relation(ops::SQUARE_CAP),
+    '⊔' => Token::Relation(ops::SQUARE_CUP),
+    '⊕' => Token::Relation(ops::CIRCLED_PLUS),
+    '⊖' => Token::Relation(ops::CIRCLED_MINUS),
+    '⊗' => Token::Relation(ops::CIRCLED_TIMES),
+    '⊘' => Token::Relation(ops::CIRCLED_DIVISION_SLASH),
+    '⊙' => Token::Relation(ops::CIRCLED_DOT_OPERATOR),
+    '⊚' => Token::Relation(ops::CIRCLED_RING_OPERATOR),
+    '⊛' => Token::Relation(ops::CIRCLED_ASTERISK_OPERATOR),
+    '⊝' => Token::Relation(ops::CIRCLED_DASH),
+    '⊞' => Token::Relation(ops::SQUARED_PLUS),
+    '⊟' => Token::Relation(ops::SQUARED_MINUS),
+    '⊠' => Token::Relation(ops::SQUARED_TIMES),
+    '⊡' => Token::Relation(ops::SQUARED_DOT_OPERATOR),
+    '⊢' => Token::Relation(ops::RIGHT_TACK),
+    '⊣' => Token::Relation(ops::LEFT_TACK),
+    '⊨' => Token::Relation(ops::TRUE),
+    '⊩' => Token::Relation(ops::FORCES),
+    '⊪' => Token::Relation(ops::TRIPLE_VERTICAL_BAR_RIGHT_TURNSTILE),
+    '⊫' => Token::Relation(ops::DOUBLE_VERTICAL_BAR_DOUBLE_RIGHT_TURNSTILE),
+    '⊬' => Token::Relation(ops::DOES_NOT_PROVE),
+    '⊭' => Token::Relation(ops::NOT_TRUE),
+    '⊮' => Token::Relation(ops::DOES_NOT_FORCE),
+    '⊯' => Token::Relation(ops::NEGATED_DOUBLE_VERTICAL_BAR_DOUBLE_RIGHT_TURNSTILE),
+    '⊲' => Token::Relation(ops::NORMAL_SUBGROUP_OF),
+    '⊳' => Token::Relation(ops::CONTAINS_AS_NORMAL_SUBGROUP),
+    '⊴' => Token::Relation(ops::NORMAL_SUBGROUP_OF_OR_EQUAL_TO),
+    '⊵' => Token::Relation(ops::CONTAINS_AS_NORMAL_SUBGROUP_OR_EQUAL_TO),
+    '⊸' => Token::Relation(ops::MULTIMAP),
+    '⊺' => Token::Relation(ops::INTERCALATE),
+    '⊻' => Token::Relation(ops::XOR),
+    '⊼' => Token::Relation(ops::NAND),
+    '⋀' => Token::BigOp(ops::N_ARY_LOGICAL_AND),
+    '⋁' => Token::BigOp(ops::N_ARY_LOGICAL_OR),
+    '⋂' => Token::BigOp(ops::N_ARY_INTERSECTION),
+    '⋃' => Token::BigOp(ops::N_ARY_UNION),
+    '⋄' => Token::Relation(ops::DIAMOND_OPERATOR),
+    '⋆' => Token::Relation(ops::STAR_OPERATOR),
+    '⋇' => Token::Relation(ops::DIVISION_TIMES),
+    '⋈' => Token::Relation(ops::BOWTIE),
+    '⋉' => Token::Relation(ops::LEFT_NORMAL_FACTOR_SEMIDIRECT_PRODUCT),
+    '⋊' => Token::Relation(ops::RIGHT_NORMAL_FACTOR_SEMIDIRECT_PRODUCT),
+    '⋋' => Token::Relation(ops::LEFT_SEMIDIRECT_PRODUCT),
+    '⋌' => Token::Relation(ops::RIGHT_SEMIDIRECT_PRODUCT),
+    '⋍' => Token::Relation(ops::REVERSED_TILDE_EQUALS),
+    '⋎' => Token::Relation(ops::CURLY_LOGICAL_OR),
+    '⋏' => Token::Relation(ops::CURLY_LOGICAL_AND),
+    '⋐' => Token::Relation(ops::DOUBLE_SUBSET),
+    '⋑' => Token::Relation(ops::DOUBLE_SUPERSET),
+    '⋒' => Token::Relation(ops::DOUBLE_INTERSECTION),
+    '⋓' => Token::Relation(ops::DOUBLE_UNION),
+    '⋔' => Token::Relation(ops::PITCHFORK),
+    '⋖' => Token::Relation(ops::LESS_THAN_WITH_DOT),
+    '⋘' => Token::Relation(ops::VERY_MUCH_LESS_THAN),
+    '⋚' => Token::Relation(ops::LESS_THAN_EQUAL_TO_OR_GREATER_THAN),
+    '⋞' => Token::Relation(ops::EQUAL_TO_OR_PRECEDES),
+    '⋟' => Token::Relation(ops::EQUAL_TO_OR_SUCCEEDS),
+    '⋠' => Token::Relation(ops::DOES_NOT_PRECEDE_OR_EQUAL),
+    '⋡' => Token::Relation(ops::DOES_NOT_SUCCEED_OR_EQUAL),
+    '⋨' => Token::Relation(ops::PRECEDES_BUT_NOT_EQUIVALENT_TO),
+    '⋩' => Token::Relation(ops::SUCCEEDS_BUT_NOT_EQUIVALENT_TO),
+    '⋮' => Token::Relation(ops::VERTICAL_ELLIPSIS),
+    '⋱' => Token::Relation(ops::DOWN_RIGHT_DIAGONAL_ELLIPSIS),
+    '⌈' => Token::Delimiter(ops::LEFT_CEILING),
+    '⌉' => Token::Delimiter(ops::RIGHT_CEILING),
+    '⌊' => Token::Delimiter(ops::LEFT_FLOOR),
+    '⌋' => Token::Delimiter(ops::RIGHT_FLOOR),
+    '⌢' => Token::Relation(ops::FROWN),
+    '⌣' => Token::Relation(ops::SMILE),
+    '⟂' => Token::Relation(ops::PERPENDICULAR),
+    '⟦' => Token::Delimiter(ops::MATHEMATICAL_LEFT_WHITE_SQUARE_BRACKET),
+    '⟧' => Token::Delimiter(ops::MATHEMATICAL_RIGHT_WHITE_SQUARE_BRACKET),
+    '⟨' => Token::Delimiter(ops::MATHEMATICAL_LEFT_ANGLE_BRACKET),
+    '⟩' => Token::Delimiter(ops::MATHEMATICAL_RIGHT_ANGLE_BRACKET),
+    '⟮' => Token::Delimiter(ops::MATHEMATICAL_LEFT_FLATTENED_PARENTHESIS),
+    '⟯' => Token::Delimiter(ops::MATHEMATICAL_RIGHT_FLATTENED_PARENTHESIS),
+    '⟵' => Token::Relation(ops::LONG_LEFTWARDS_ARROW),
+    '⟶' => Token::Relation(ops::LONG_RIGHTWARDS_ARROW),
+    '⟷' => Token::Relation(ops::LONG_LEFT_RIGHT_ARROW),
+    '⟸' => Token::Relation(ops::LONG_LEFTWARDS_DOUBLE_ARROW),
+    '⟹' => Token::Relation(ops::LONG_RIGHTWARDS_DOUBLE_ARROW),
+    '⟺' => Token::Relation(ops::LONG_LEFT_RIGHT_DOUBLE_ARROW),
+    '⟼' => Token::Relation(ops::LONG_RIGHTWARDS_ARROW_FROM_BAR),
+    '⤙' => Token::Relation(ops::LEFTWARDS_ARROW_TAIL),
+    '⤚' => Token::Relation(ops::RIGHTWARDS_ARROW_TAIL),
+    '⦃' => Token::Delimiter(ops::LEFT_WHITE_CURLY_BRACKET),
+    '⦄' => Token::Delimiter(ops::RIGHT_WHITE_CURLY_BRACKET),
+    '⦇' => Token::Delimiter(ops::Z_NOTATION_LEFT_IMAGE_BRACKET),
+    '⦈' => Token::Delimiter(ops::Z_NOTATION_RIGHT_IMAGE_BRACKET),
+    '⦉' => Token::Delimiter(ops::Z_NOTATION_LEFT_BINDING_BRACKET),
+    '⦊' => Token::Delimiter(ops::Z_NOTATION_RIGHT_BINDING_BRACKET),
+    '⧄' => Token::Relation(ops::SQUARED_RISING_DIAGONAL_SLASH),
+    '⧅' => Token::Relation(ops::SQUARED_FALLING_DIAGONAL_SLASH),
+    '⧈' => Token::Relation(ops::SQUARED_SQUARE),
+    '⨀' => Token::BigOp(ops::N_ARY_CIRCLED_DOT_OPERATOR),
+    '⨁' => Token::BigOp(ops::N_ARY_CIRCLED_PLUS_OPERATOR),
+    '⨂' => Token::BigOp(ops::N_ARY_CIRCLED_TIMES_OPERATOR),
+    '⨃' => Token::BigOp(ops::N_ARY_UNION_OPERATOR_WITH_DOT),
+    '⨄' => Token::BigOp(ops::N_ARY_UNION_OPERATOR_WITH_PLUS),
+    '⨅' => Token::BigOp(ops::N_ARY_SQUARE_INTERSECTION_OPERATOR),
+    '⨆' => Token::BigOp(ops::N_ARY_SQUARE_UNION_OPERATOR),
+    '⨇' => Token::BigOp(ops::TWO_LOGICAL_AND_OPERATOR),
+    '⨈' => Token::BigOp(ops::TWO_LOGICAL_OR_OPERATOR),
+    '⨉' => Token::BigOp(ops::N_ARY_TIMES_OPERATOR),
+    '⨋' => Token::BigOp(ops::SUMMATION_WITH_INTEGRAL),
+    '⨌' => Token::Integral(ops::QUADRUPLE_INTEGRAL_OPERATOR),
+    '⨍' => Token::Integral(ops::FINITE_PARTL_INTEGRAL),
+    '⨎' => Token::Integral(ops::INTEGRAL_WITH_DOUBLE_STROKE),
+    '⨏' => Token::Integral(ops::INTEGRAL_AVERAGE_WITH_SLASH),
+    '⨐' => Token::Integral(ops::CIRCULATION_FUNCTION),
+    '⨑' => Token::Integral(ops::ANTICLOCKWISE_INTEGRATION),
+    '⨟' => Token::Relation(ops::Z_NOTATION_SCHEMA_COMPOSITION),
+    '⨿' => Token::Relation(ops::AMALGAMATION_OR_COPRODUCT),
+    '⩞' => Token::Relation(ops::LOGICAL_AND_WITH_DOUBLE_OVERBAR),
+    '⩦' => Token::Relation(ops::EQUALS_SIGN_WITH_DOT_BELOW),
+    '⩽' => Token::Relation(ops::LESS_THAN_OR_SLANTED_EQUAL_TO),
+    '⩾' => Token::Relation(ops::GREATER_THAN_OR_SLANTED_EQUAL_TO),
+    '⪅' => Token::Relation(ops::LESS_THAN_OR_APPROXIMATE),
+    '⪆' => Token::Relation(ops::GREATER_THAN_OR_APPROXIMATE),
+    '⪇' => Token::Relation(ops::LESS_THAN_AND_SINGLE_LINE_NOT_EQUAL_TO),
+    '⪈' => Token::Relation(ops::GREATER_THAN_AND_SINGLE_LINE_NOT_EQUAL_TO),
+    '⪋' => Token::Relation(ops::LESS_THAN_ABOVE_DOUBLE_LINE_EQUAL_ABOVE_GREATER_THAN),
+    '⪕' => Token::Relation(ops::SLANTED_EQUAL_TO_OR_LESS_THAN),
+    '⪖' => Token::Relation(ops::SLANTED_EQUAL_TO_OR_GREATER_THAN),
+    '⪯' => Token::Relation(ops::PRECEDES_ABOVE_SINGLE_LINE_EQUALS_SIGN),
+    '⪰' => Token::Relation(ops::SUCCEEDS_ABOVE_SINGLE_LINE_EQUALS_SIGN),
+    '⪵' => Token::Relation(ops::PRECEDES_ABOVE_NOT_EQUAL_TO),
+    '⪶' => Token::Relation(ops::SUCCEEDS_ABOVE_NOT_EQUAL_TO),
+    '⪷' => Token::Relation(ops::PRECEDES_ABOVE_ALMOST_EQUAL_TO),
+    '⪸' => Token::Relation(ops::SUCCEEDS_ABOVE_ALMOST_EQUAL_TO),
+    '⪹' => Token::Relation(ops::PRECEDES_ABOVE_NOT_ALMOST_EQUAL_TO),
+    '⪺' => Token::Relation(ops::SUCCEEDS_ABOVE_NOT_ALMOST_EQUAL_TO),
+    '⫋' => Token::Relation(ops::SUBSET_OF_ABOVE_NOT_EQUAL_TO),
+    '⫌' => Token::Relation(ops::SUPERSET_OF_ABOVE_NOT_EQUAL_TO),
+    '﹨' => Token::Relation(ops::SMALL_REVERSE_SOLIDUS),
+};
+
+/// Commands which are only valid in text mode.
+static TEXT_COMMANDS: phf::Map<&'static str, Token> = phf::phf_map! {
+    "\"" => Token::TextAccent('\u{308}'),
+    "'" => Token::TextAccent('\u{301}'),
+    "." => Token::TextAccent('\u{307}'),
+    "=" => Token::TextAccent('\u{304}'),
+    "AA" => Token::Letter('Å'),
+    "AE" => Token::Letter('Æ'),
+    "DH" => Token::Letter('Ð'),
+    "H" => Token::TextAccent('\u{30B}'),
+    "L" => Token::Letter('Ł'),
+    "O" => Token::Letter('Ø'),
+    "OE" => Token::Letter('Œ'),
+    "TH" => Token::Letter('Þ'),
+    "^" => Token::TextAccent('\u{302}'),
+    "`" => Token::TextAccent('\u{300}'),
+    "aa" => Token::Letter('å'),
+    "ae" => Token::Letter('æ'),
+    "b" => Token::TextAccent('\u{331}'),
+    "c" => Token::TextAccent('\u{327}'),
+    "d" => Token::TextAccent('\u{323}'),
+    "dh" => Token::Letter('ð'),
+    "i" => Token::Letter('ı'),
+    "j" => Token::Letter('ȷ'),
+    "k" => Token::TextAccent('\u{328}'),
+    "l" => Token::Letter('ł'),
+    "o" => Token::Letter('ø'),
+    "oe" => Token::Letter('œ'),
+    "r" => Token::TextAccent('\u{30A}'),
+    "ss" => Token::Letter('ß'),
+    "textasciicircum" => Token::Letter('^'),
+    "textasciitilde" => Token::Letter('~'),
+    "textbackslash" => Token::Letter('\\'),
+    "textbar" => Token::Letter('|'),
+    "textemdash" => Token::Letter('—'),
+    "textendash" => Token::Letter('–'),
+    "textgreater" => Token::Letter('>'),
+    "textless" => Token::Letter('<'),
+    "textunderscore" => Token::Letter('_'),
+    "textyen" => Token::Letter('¥'),
+    "th" => Token::Letter('þ'),
+    "u" => Token::TextAccent('\u{306}'),
+    "v" => Token::TextAccent('\u{30C}'),
+    "~" => Token::TextAccent('\u{303}'),
+};
+
+/// Get the token for a command in math mode.
+///
+/// Unless `lenient` is set, text-mode commands give `Token::TextOnlyCommand`.
+pub fn get_command(command: &str, lenient: bool) -> Token<'_> {
     match COMMANDS.get(command) {
         Some(token) => *token,
+        None => match TEXT_COMMANDS.get(command) {
+            Some(token) if lenient => *token,
+            Some(_) => Token::TextOnlyCommand(command),
+            None => Token::UnknownCommand(command),
+        },
+    }
+}
+
+/// Get the token for a command in text mode.
+///
+/// The text-mode commands take precedence over math commands of the same name.
+/// Unless `lenient` is set, math commands which have no meaning in text
+/// give `Token::MathOnlyCommand`.
+pub fn get_text_command(command: &str, lenient: bool) -> Token<'_> {
+    if let Some(token) = TEXT_COMMANDS.get(command) {
+        return *token;
+    }
+    match COMMANDS.get(command) {
+        Some(token) if lenient || is_valid_in_text(command, token) => *token,
+        Some(_) => Token::MathOnlyCommand(command),
         None => Token::UnknownCommand(command),
     }
 }
 
+/// Function names of Spanish texts, as in babel-spanish.
+static SPANISH_FUNCTIONS: phf::Map<&'static str, &'static str> = phf::phf_map! {
+    "arcsen" => "arcsen",
+    "arcsin" => "arcsen",
+    "arctan" => "arctg",
+    "arctg" => "arctg",
+    "cosec" => "cosec",
+    "cot" => "cotg",
+    "cotg" => "cotg",
+    "csc" => "cosec",
+    "sen" => "sen",
+    "senh" => "senh",
+    "sin" => "sen",
+    "sinh" => "senh",
+    "tan" => "tg",
+    "tanh" => "tgh",
+    "tg" => "tg",
+    "tgh" => "tgh",
+};
+
+/// Function names of Russian texts.
+static RUSSIAN_FUNCTIONS: phf::Map<&'static str, &'static str> = phf::phf_map! {
+    "arcctg" => "arcctg",
+    "arctan" => "arctg",
+    "arctg" => "arctg",
+    "ch" => "ch",
+    "cosec" => "cosec",
+    "cosh" => "ch",
+    "cot" => "ctg",
+    "coth" => "cth",
+    "csc" => "cosec",
+    "ctg" => "ctg",
+    "cth" => "cth",
+    "sh" => "sh",
+    "sinh" => "sh",
+    "tan" => "tg",
+    "tanh" => "th",
+    "tg" => "tg",
+    "th" => "th",
+};
+
+/// Function names of French texts.
+static FRENCH_FUNCTIONS: phf::Map<&'static str, &'static str> = phf::phf_map! {
+    "argch" => "argch",
+    "argsh" => "argsh",
+    "argth" => "argth",
+    "ch" => "ch",
+    "cosh" => "ch",
+    "cot" => "cotan",
+    "cotan" => "cotan",
+    "sh" => "sh",
+    "sinh" => "sh",
+    "tanh" => "th",
+    "th" => "th",
+};
+
+/// Get the token for a localized function command in math mode, like `\sen` in Spanish.
+///
+/// The commands of the standard table, like `\sin`, are only localized
+/// if `rename_standard` is `true`.
+pub fn get_localized_command(
+    command: &str,
+    locale: OperatorLocale,
+    rename_standard: bool,
+) -> Option<Token<'static>> {
+    let functions = match locale {
+        OperatorLocale::English => return None,
+        OperatorLocale::French => &FRENCH_FUNCTIONS,
+        OperatorLocale::Russian => &RUSSIAN_FUNCTIONS,
+        OperatorLocale::Spanish => &SPANISH_FUNCTIONS,
+    };
+    let name = functions.get(command)?;
+    if rename_standard || !COMMANDS.contains_key(command) {
+        Some(Token::Function(name))
+    } else {
+        None
+    }
+}
+
+/// Whether a command from the math table can also be used in text mode.
+fn is_valid_in_text(command: &str, token: &Token) -> bool {
+    matches!(
+        token,
+        Token::Text(_)
+            | Token::Space(_)
+            | Token::NonBreakingSpace
+            | Token::NewLine
+            | Token::InlineMathBegin
+            | Token::InlineMathEnd
+            | Token::FontSize(_)
+    ) || matches!(
+        command,
+        "#" | "$"
+            | "%"
+            | "&"
+            | "_"
+            | "{"
+            | "}"
+            | "P"
+            | "S"
+            | "allowbreak"
+            | "checkmark"
+            | "copyright"
+            | "dag"
+            | "ddag"
+            | "dots"
+            | "euro"
+            | "ldots"
+            | "pounds"
+            | "rq"
+    )
+}
+
+/// Combine a letter with the combining character of a text accent.
+///
+/// Returns `None` if Unicode has no precomposed character for the pair.
+pub fn compose_accent(base: char, accent: char) -> Option<char> {
+    // For each accent: the letters it can be combined with, and the results.
+    static ACCENTED: [(char, &str, &str); 15] = [
+        ('\u{300}', "AEINOUWYaeinouwy", "ÀÈÌǸÒÙẀỲàèìǹòùẁỳ"),
+        (
+            '\u{301}',
+            "ACEGIKLMNOPRSUWYZacegiklmnoprsuwyz",
+            "ÁĆÉǴÍḰĹḾŃÓṔŔŚÚẂÝŹáćéǵíḱĺḿńóṕŕśúẃýź",
+        ),
+        (
+            '\u{302}',
+            "ACEGHIJOSUWYZaceghijosuwyz",
+            "ÂĈÊĜĤÎĴÔŜÛŴŶẐâĉêĝĥîĵôŝûŵŷẑ",
+        ),
+        ('\u{303}', "AEINOUVYaeinouvy", "ÃẼĨÑÕŨṼỸãẽĩñõũṽỹ"),
+        ('\u{304}', "AEGIOUYaegiouy", "ĀĒḠĪŌŪȲāēḡīōūȳ"),
+        ('\u{306}', "AEGIOUaegiou", "ĂĔĞĬŎŬăĕğĭŏŭ"),
+        (
+            '\u{307}',
+            "ABCDEFGHIMNOPRSTWXYZabcdefghmnoprstwxyz",
+            "ȦḂĊḊĖḞĠḢİṀṄȮṖṘṠṪẆẊẎŻȧḃċḋėḟġḣṁṅȯṗṙṡṫẇẋẏż",
+        ),
+        ('\u{308}', "AEHIOUWXYaehiotuwxy", "ÄËḦÏÖÜẄẌŸäëḧïöẗüẅẍÿ"),
+        ('\u{30A}', "AUauwy", "ÅŮåůẘẙ"),
+        ('\u{30B}', "OUou", "ŐŰőű"),
+        (
+            '\u{30C}',
+            "ACDEGHIKLNORSTUZacdeghijklnorstuz",
+            "ǍČĎĚǦȞǏǨĽŇǑŘŠŤǓŽǎčďěǧȟǐǰǩľňǒřšťǔž",
+        ),
+        (
+            '\u{323}',
+            "ABDEHIKLMNORSTUVWYZabdehiklmnorstuvwyz",
+            "ẠḄḌẸḤỊḲḶṂṆỌṚṢṬỤṾẈỴẒạḅḍẹḥịḳḷṃṇọṛṣṭụṿẉỵẓ",
+        ),
+        (
+            '\u{327}',
+            "CDEGHKLNRSTcdeghklnrst",
+            "ÇḐȨĢḨĶĻŅŖŞŢçḑȩģḩķļņŗşţ",
+        ),
+        ('\u{328}', "AEIOUaeiou", "ĄĘĮǪŲąęįǫų"),
+        ('\u{331}', "BDKLNRTZbdhklnrtz", "ḆḎḴḺṈṞṮẔḇḏẖḵḻṉṟṯẕ"),
+    ];
+    let (_, bases, composed) = ACCENTED.iter().find(|(mark, _, _)| *mark == accent)?;
+    // Accents on the dotless `\i` and `\j` replace the dot.
+    let base = match base {
+        'ı' => 'i',
+        'ȷ' => 'j',
+        base => base,
+    };
+    let index = bases.chars().position(|c| c == base)?;
+    composed.chars().nth(index)
+}
+
+/// Classify a character that was typed directly in the input.
+///
+/// Characters without an entry (including the mathematical alphanumerics)
+/// are treated as letters.
+pub fn get_unicode_symbol(ch: char) -> Token<'static> {
+    match UNICODE_SYMBOLS.get(&ch) {
+        Some(token) => *token,
+        None => Token::Letter(ch),
+    }
+}
+
+/// Get the precomposed negation of a relation, if Unicode has one.
+///
+/// The pairs are all characters whose canonical decomposition in `UnicodeData.txt`
+/// is `<base> U+0338`. Relations without an entry here have to be negated by
+/// appending U+0338 COMBINING LONG SOLIDUS OVERLAY.
 pub fn get_negated_op(op: Rel) -> Option<Rel> {
     match op {
         ops::ALMOST_EQUAL_TO => Some(ops::NOT_ALMOST_EQUAL_TO),
-        ops::APPROXIMATELY_EQUAL_TO => Some(ops::NOT_ASYMPTOTICALLY_EQUAL_TO),
+        ops::APPROXIMATELY_EQUAL_TO => Some(ops::NEITHER_APPROXIMATELY_NOR_ACTUALLY_EQUAL_TO),
+        ops::ASYMPTOTICALLY_EQUAL_TO => Some(ops::NOT_ASYMPTOTICALLY_EQUAL_TO),
+        ops::CONTAINS_AS_MEMBER => Some(ops::DOES_NOT_CONTAIN_AS_MEMBER),
+        ops::CONTAINS_AS_NORMAL_SUBGROUP => Some(ops::DOES_NOT_CONTAIN_AS_NORMAL_SUBGROUP),
+        ops::CONTAINS_AS_NORMAL_SUBGROUP_OR_EQUAL_TO => {
+            Some(ops::DOES_NOT_CONTAIN_AS_NORMAL_SUBGROUP_OR_EQUAL)
+        }
+        ops::DIVIDES => Some(ops::DOES_NOT_DIVIDE),
         ops::DOUBLE_VERTICAL_BAR_DOUBLE_RIGHT_TURNSTILE => {
             Some(ops::NEGATED_DOUBLE_VERTICAL_BAR_DOUBLE_RIGHT_TURNSTILE)
         }
         ops::ELEMENT_OF => Some(ops::NOT_AN_ELEMENT_OF),
+        ops::EQUALS_SIGN => Some(ops::NOT_EQUAL_TO),
+        ops::EQUIVALENT_TO => Some(ops::NOT_EQUIVALENT_TO),
         ops::FORCES => Some(ops::DOES_NOT_FORCE),
-        ops::GREATER_THAN_OVER_EQUAL_TO => Some(ops::NEITHER_GREATER_THAN_NOR_EQUAL_TO),
+        ops::GREATER_THAN_OR_EQUAL_TO => Some(ops::NEITHER_GREATER_THAN_NOR_EQUAL_TO),
+        ops::GREATER_THAN_OR_EQUIVALENT_TO => Some(ops::NEITHER_GREATER_THAN_NOR_EQUIVALENT_TO),
+        ops::GREATER_THAN_OR_LESS_THAN => Some(ops::NEITHER_GREATER_THAN_NOR_LESS_THAN),
+        ops::IDENTICAL_TO => Some(ops::NOT_IDENTICAL_TO),
+        ops::LEFTWARDS_ARROW => Some(ops::LEFTWARDS_ARROW_WITH_STROKE),
+        ops::LEFTWARDS_DOUBLE_ARROW => Some(ops::LEFTWARDS_DOUBLE_ARROW_WITH_STROKE),
+        ops::LEFT_RIGHT_ARROW => Some(ops::LEFT_RIGHT_ARROW_WITH_STROKE),
+        ops::LEFT_RIGHT_DOUBLE_ARROW => Some(ops::LEFT_RIGHT_DOUBLE_ARROW_WITH_STROKE),
         ops::LESS_THAN_OR_EQUAL_TO => Some(ops::NEITHER_LESS_THAN_NOR_EQUAL_TO),
+        ops::LESS_THAN_OR_EQUIVALENT_TO => Some(ops::NEITHER_LESS_THAN_NOR_EQUIVALENT_TO),
+        ops::LESS_THAN_OR_GREATER_THAN => Some(ops::NEITHER_LESS_THAN_NOR_GREATER_THAN),
+        ops::NONFORKING => Some(ops::FORKING),
+        ops::NORMAL_SUBGROUP_OF => Some(ops::NOT_NORMAL_SUBGROUP_OF),
+        ops::NORMAL_SUBGROUP_OF_OR_EQUAL_TO => Some(ops::NOT_NORMAL_SUBGROUP_OF_OR_EQUAL_TO),
+        ops::PARALLEL_TO => Some(ops::NOT_PARALLEL_TO),
         ops::PRECEDES => Some(ops::DOES_NOT_PRECEDE),
+        ops::PRECEDES_OR_EQUAL_TO => Some(ops::DOES_NOT_PRECEDE_OR_EQUAL),
+        ops::RIGHTWARDS_ARROW => Some(ops::RIGHTWARDS_ARROW_WITH_STROKE),
+        ops::RIGHTWARDS_DOUBLE_ARROW => Some(ops::RIGHTWARDS_DOUBLE_ARROW_WITH_STROKE),
         ops::RIGHT_TACK => Some(ops::DOES_NOT_PROVE),
+        ops::SQUARE_IMAGE_OF_OR_EQUAL_TO => Some(ops::NOT_SQUARE_IMAGE_OF_OR_EQUAL_TO),
+        ops::SQUARE_ORIGINAL_OF_OR_EQUAL_TO => Some(ops::NOT_SQUARE_ORIGINAL_OF_OR_EQUAL_TO),
         ops::SUBSET_OF => Some(ops::NOT_A_SUBSET_OF),
         ops::SUBSET_OF_OR_EQUAL_TO => Some(ops::NEITHER_A_SUBSET_OF_NOR_EQUAL_TO),
         ops::SUCCEEDS => Some(ops::DOES_NOT_SUCCEED),
+        ops::SUCCEEDS_OR_EQUAL_TO => Some(ops::DOES_NOT_SUCCEED_OR_EQUAL),
         ops::SUPERSET_OF => Some(ops::NOT_A_SUPERSET_OF),
         ops::SUPERSET_OF_OR_EQUAL_TO => Some(ops::NEITHER_A_SUPERSET_OF_NOR_EQUAL_TO),
+        ops::THERE_EXISTS => Some(ops::THERE_DOES_NOT_EXIST),
+        ops::TILDE_OPERATOR => Some(ops::NOT_TILDE),
         ops::TRUE => Some(ops::NOT_TRUE),
         _ => None,
     }
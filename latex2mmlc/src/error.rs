@@ -25,6 +25,8 @@ pub enum LatexErrKind<'source> {
     UnparsableEnvName,
     UnknownEnvironment(&'source str),
     UnknownCommand(&'source str),
+    TextOnlyCommand(&'source str),
+    MathOnlyCommand(&'source str),
     MismatchedEnvironment {
         expected: &'source str,
         got: &'source str,
@@ -34,6 +36,14 @@ pub enum LatexErrKind<'source> {
         correct_place: Place,
     },
     ExpectedText(&'static str),
+    UnsupportedLength(&'source str),
+    InvalidColumnSpec(&'source str),
+    InvalidColumnCount(&'source str),
+    InvalidColumnRange(&'source str),
+    MissingPremises(u8),
+    UnfinishedProofTree(usize),
+    InvalidCodePoint(&'source str),
+    SurrogateCodePoint(&'source str),
 }
 
 #[derive(Debug, AsRefStr)]
@@ -45,6 +55,16 @@ pub enum Place {
     BeforeSomeOps,
     #[strum(serialize = r"after an identifier or operator")]
     AfterOpOrIdent,
+    #[strum(serialize = r"inside \text")]
+    InText,
+    #[strum(serialize = r"directly inside a group")]
+    InsideGroup,
+    #[strum(serialize = r"inside \begin{prooftree}")]
+    InProofTree,
+    #[strum(serialize = r"at the start of the index of \sqrt")]
+    StartOfRootIndex,
+    #[strum(serialize = r"inside an array or matrix")]
+    InTable,
 }
 
 impl LatexErrKind<'_> {
@@ -80,6 +100,12 @@ impl LatexErrKind<'_> {
                 "Unknown environment \"".to_string() + environment + "\"."
             }
             LatexErrKind::UnknownCommand(cmd) => "Unknown command \"\\".to_string() + cmd + "\".",
+            LatexErrKind::TextOnlyCommand(cmd) => {
+                "The command \"\\".to_string() + cmd + "\" is only valid in text mode."
+            }
+            LatexErrKind::MathOnlyCommand(cmd) => {
+                "The command \"\\".to_string() + cmd + "\" is only valid in math mode."
+            }
             LatexErrKind::MismatchedEnvironment { expected, got } => {
                 "Expected \"\\end{".to_string() + expected + "}\", but got \"\\end{" + got + "}\"."
             }
@@ -91,6 +117,34 @@ impl LatexErrKind<'_> {
                     + "."
             }
             LatexErrKind::ExpectedText(place) => "Expected text in ".to_string() + place + ".",
+            LatexErrKind::UnsupportedLength(length) => {
+                "Unsupported length \"".to_string() + length + "\"."
+            }
+            LatexErrKind::InvalidColumnSpec(spec) => {
+                "Invalid column specification \"".to_string() + spec + "\"."
+            }
+            LatexErrKind::InvalidColumnCount(count) => {
+                "Invalid number of columns \"".to_string() + count + "\"."
+            }
+            LatexErrKind::InvalidColumnRange(range) => {
+                "Invalid column range \"".to_string() + range + "\"."
+            }
+            LatexErrKind::MissingPremises(premises) => {
+                "The inference rule needs ".to_string()
+                    + &premises.to_string()
+                    + " premises, but there are fewer."
+            }
+            LatexErrKind::UnfinishedProofTree(parts) => {
+                "The proof tree consists of ".to_string()
+                    + &parts.to_string()
+                    + " separate proofs instead of one."
+            }
+            LatexErrKind::InvalidCodePoint(code) => {
+                "Invalid code point in \"".to_string() + code + "\"."
+            }
+            LatexErrKind::SurrogateCodePoint(code) => {
+                "Surrogate code point in \"".to_string() + code + "\" is not a character."
+            }
         }
     }
 }
@@ -4,18 +4,27 @@ use std::str::CharIndices;
 
 use mathml_renderer::ops;
 
-use crate::commands::get_command;
-use crate::error::GetUnwrap;
+use crate::commands::{get_command, get_localized_command, get_text_command, get_unicode_symbol};
+use crate::error::{GetUnwrap, LatexErrKind, LatexError};
 use crate::token::{Digit, TokLoc, Token};
+use crate::OperatorLocale;
 
 /// Lexer
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 pub(crate) struct Lexer<'source> {
     input: CharIndices<'source>,
     peek: (usize, char),
     input_string: &'source str,
     pub input_length: usize,
     pub text_mode: bool,
+    /// Accept commands in the wrong mode, like `\AA` in math mode.
+    pub lenient_modes: bool,
+    /// The commands which were accepted in the wrong mode, with the error they would
+    /// have given without `lenient_modes`.
+    pub warnings: Vec<LatexError<'source>>,
+    /// The language of the function names, for commands like `\sen`.
+    pub operator_locale: OperatorLocale,
+    pub localize_standard_operators: bool,
 }
 
 impl<'source> Lexer<'source> {
@@ -27,6 +36,10 @@ impl<'source> Lexer<'source> {
             input_string: input,
             input_length: input.len(),
             text_mode: false,
+            lenient_modes: false,
+            warnings: Vec::new(),
+            operator_locale: OperatorLocale::English,
+            localize_standard_operators: false,
         };
         lexer.read_char(); // Initialize `peek`.
         lexer
@@ -96,6 +109,79 @@ impl<'source> Lexer<'source> {
         }
     }
 
+    /// Read any characters until the matching `}`, like the column specification of an `array`.
+    ///
+    /// Nested groups, as in `p{2cm}`, and escaped braces are part of the result.
+    /// Returns `None` if the input ends before the `}`.
+    pub(crate) fn read_raw_group(&mut self) -> Option<&'source str> {
+        let start = self.peek.0;
+
+        let mut depth = 0usize;
+        while self.peek.0 < self.input_length {
+            match self.peek.1 {
+                '}' if depth == 0 => break,
+                '}' => depth -= 1,
+                '{' => depth += 1,
+                '\\' => {
+                    // Skip the escaped character.
+                    self.read_char();
+                }
+                _ => {}
+            }
+            self.read_char();
+        }
+
+        let closing = self.read_char();
+        if closing.1 == '}' {
+            let end = closing.0;
+            // SAFETY: we got `start` and `end` from `CharIndices`, so they are valid bounds.
+            Some(self.input_string.get_unwrap(start..end))
+        } else {
+            None
+        }
+    }
+
+    /// Read a length like `2pt` or `0.5em`, skipping whitespace before it.
+    pub(crate) fn read_length(&mut self) -> &'source str {
+        self.skip_whitespace();
+        let start = self.peek.0;
+        while self.peek.1.is_ascii_digit() || self.peek.1 == '.' {
+            self.read_char();
+        }
+        while self.peek.1.is_ascii_alphabetic() {
+            self.read_char();
+        }
+        let end = self.peek.0;
+        // SAFETY: we got `start` and `end` from `CharIndices`, so they are valid bounds.
+        self.input_string.get_unwrap(start..end)
+    }
+
+    /// Read the rest of a separator in braces, like the `,}` of `3{,}14`,
+    /// if `is_separator` accepts it and it is followed by a digit.
+    ///
+    /// The opening brace must already have been read.
+    pub(crate) fn read_braced_separator(
+        &mut self,
+        is_separator: impl Fn(char) -> bool,
+    ) -> Option<char> {
+        let separator = self.peek.1;
+        // SAFETY: we got the start from `CharIndices`, so it is a valid bound.
+        let rest = self
+            .input_string
+            .get_unwrap(self.peek.0..self.input_length)
+            .as_bytes();
+        if is_separator(separator)
+            && rest.get(1) == Some(&b'}')
+            && rest.get(2).is_some_and(u8::is_ascii_digit)
+        {
+            self.read_char();
+            self.read_char();
+            Some(separator)
+        } else {
+            None
+        }
+    }
+
     /// Check if the next character is a digit.
     pub(crate) fn is_next_digit(&mut self) -> bool {
         if !self.text_mode {
@@ -123,12 +209,75 @@ impl<'source> Lexer<'source> {
             }
             return self.next_token();
         }
+        if self.text_mode {
+            if let Some(tok) = self.text_token(ch) {
+                return TokLoc(loc, tok);
+            }
+        }
         let tok = match ch {
             '\u{0}' => Token::EOF,
+            '^' => self.read_caret_notation(loc).unwrap_or(Token::Circumflex),
+            '\\' => {
+                let cmd = match self.read_command() {
+                    "unicode" => self.read_code_point(loc, true),
+                    "char" => self.read_code_point(loc, false),
+                    cmd if self.text_mode => get_text_command(cmd, false),
+                    cmd => get_localized_command(
+                        cmd,
+                        self.operator_locale,
+                        self.localize_standard_operators,
+                    )
+                    .unwrap_or_else(|| get_command(cmd, false)),
+                };
+                // In lenient mode, a command of the other mode is accepted with a warning.
+                let cmd = match cmd {
+                    Token::TextOnlyCommand(name) if self.lenient_modes => {
+                        let kind = LatexErrKind::TextOnlyCommand(name);
+                        self.warnings.push(LatexError(loc, kind));
+                        get_command(name, true)
+                    }
+                    Token::MathOnlyCommand(name) if self.lenient_modes => {
+                        let kind = LatexErrKind::MathOnlyCommand(name);
+                        self.warnings.push(LatexError(loc, kind));
+                        get_text_command(name, true)
+                    }
+                    cmd => cmd,
+                };
+                if self.text_mode {
+                    // After a command, all whitespace is skipped, even in text mode.
+                    self.skip_whitespace();
+                }
+                cmd
+            }
+            c => self.structural_token(c),
+        };
+        TokLoc(loc, tok)
+    }
+
+    /// The token for a character as it appears in the input, including the characters
+    /// which affect the structure, like `{` or `&`.
+    fn structural_token(&self, c: char) -> Token<'source> {
+        match c {
             ' ' => Token::Letter('\u{A0}'),
-            '!' => Token::Relation(ops::EXCLAMATION_MARK),
+            '$' => Token::MathShift,
             '&' => Token::Ampersand,
-            '\'' => Token::Prime,
+            '[' => Token::SquareBracketOpen,
+            ']' => Token::SquareBracketClose,
+            '^' => Token::Circumflex,
+            '_' => Token::Underscore,
+            '{' => Token::GroupBegin,
+            '}' => Token::GroupEnd,
+            '~' => Token::NonBreakingSpace,
+            c => self.char_token(c),
+        }
+    }
+
+    /// The token for a character which does not affect the structure of the input,
+    /// i.e. one that could also have been given by its code point.
+    fn char_token(&self, c: char) -> Token<'source> {
+        match c {
+            '!' => Token::Relation(ops::EXCLAMATION_MARK),
+            '\'' => Token::Prime(1),
             '(' => Token::Delimiter(ops::LEFT_PARENTHESIS),
             ')' => Token::Delimiter(ops::RIGHT_PARENTHESIS),
             '*' => Token::Relation(ops::ASTERISK_OPERATOR),
@@ -141,35 +290,167 @@ impl<'source> Lexer<'source> {
             '<' => Token::OpLessThan,
             '=' => Token::Relation(ops::EQUALS_SIGN),
             '>' => Token::OpGreaterThan,
-            '[' => Token::SquareBracketOpen,
-            ']' => Token::SquareBracketClose,
-            '^' => Token::Circumflex,
-            '_' => Token::Underscore,
-            '{' => Token::GroupBegin,
             '|' => Token::Delimiter(ops::VERTICAL_LINE),
-            '}' => Token::GroupEnd,
-            '~' => Token::NonBreakingSpace,
-            '\\' => {
-                let cmd = get_command(self.read_command());
-                if self.text_mode {
-                    // After a command, all whitespace is skipped, even in text mode.
-                    self.skip_whitespace();
-                }
-                cmd
-            }
             c => {
                 if let Ok(digit) = Digit::try_from(c) {
                     Token::Number(digit)
-                } else {
+                } else if c.is_ascii() || self.text_mode {
                     // Some symbols like '.' and '/' are considered operators by the MathML Core spec,
                     // but in LaTeX they behave like normal identifiers (they are in the "ordinary" class 0).
                     // One might think that they could be rendered as `<mo>` with custom spacing,
                     // but then they still interact with other operators in ways that are not correct.
                     Token::Letter(c)
+                } else {
+                    match c {
+                        '′' => Token::Prime(1),
+                        '″' => Token::Prime(2),
+                        '‴' => Token::Prime(3),
+                        '⁗' => Token::Prime(4),
+                        // Non-ASCII characters are treated like the command
+                        // which produces them, e.g. `≤` like `\le`.
+                        c => get_unicode_symbol(c),
+                    }
                 }
             }
+        }
+    }
+
+    /// Read the code point after `\unicode` or `\char`.
+    ///
+    /// `\unicode{x1D4AB}` takes a hexadecimal (with `x`) or decimal number in braces;
+    /// `\char` takes a number like `"2A01` (hexadecimal), `'52001` (octal) or `10753`,
+    /// or a character like `` `a ``.
+    fn read_code_point(&mut self, start: usize, is_unicode: bool) -> Token<'source> {
+        self.skip_whitespace();
+        if is_unicode {
+            if self.peek.1 != '{' {
+                return Token::InvalidCodePoint(self.input_string.get_unwrap(start..self.peek.0));
+            }
+            self.read_char();
+            self.skip_whitespace();
+        }
+        let radix = match self.peek.1 {
+            'x' | 'X' if is_unicode => 16,
+            '"' if !is_unicode => 16,
+            '\'' if !is_unicode => 8,
+            '`' if !is_unicode => {
+                self.read_char();
+                if self.peek.1 == '\\' {
+                    self.read_char();
+                }
+                let (_, ch) = self.read_char();
+                return if ch == '\u{0}' {
+                    Token::InvalidCodePoint(self.input_string.get_unwrap(start..self.peek.0))
+                } else {
+                    self.char_token(ch)
+                };
+            }
+            _ => 10,
         };
-        TokLoc(loc, tok)
+        if radix != 10 {
+            self.read_char();
+        }
+        let digits_start = self.peek.0;
+        // Like TeX, `\char"` only takes uppercase hexadecimal digits.
+        let is_digit =
+            |c: char| c.is_digit(radix) && (is_unicode || radix != 16 || !c.is_ascii_lowercase());
+        while is_digit(self.peek.1) {
+            self.read_char();
+        }
+        let digits = self.input_string.get_unwrap(digits_start..self.peek.0);
+        if is_unicode {
+            self.skip_whitespace();
+            if self.peek.1 != '}' {
+                return Token::InvalidCodePoint(self.input_string.get_unwrap(start..self.peek.0));
+            }
+            self.read_char();
+        }
+        let span = self.input_string.get_unwrap(start..self.peek.0);
+        match code_point(u32::from_str_radix(digits, radix).ok(), span) {
+            Ok(ch) => self.char_token(ch),
+            Err(tok) => tok,
+        }
+    }
+
+    /// Read TeX's `^^xx` and `^^^^xxxx` notation, with lowercase hexadecimal digits.
+    ///
+    /// The first `^` has already been consumed. As in TeX, the character keeps the
+    /// meaning it has when typed directly, so `^^5e` is a superscript.
+    fn read_caret_notation(&mut self, start: usize) -> Option<Token<'source>> {
+        let rest = self.input_string.get(start..)?;
+        let is_hex = |digits: &str| {
+            digits
+                .bytes()
+                .all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b))
+        };
+        let len = match (rest.get(..4), rest.get(4..8), rest.get(2..4)) {
+            (Some("^^^^"), Some(digits), _) if is_hex(digits) => 8,
+            (_, _, Some(digits)) if rest.starts_with("^^") && is_hex(digits) => 4,
+            _ => return None,
+        };
+        for _ in 1..len {
+            self.read_char();
+        }
+        let digits = rest.get(len / 2..len)?;
+        let span = rest.get(..len)?;
+        let ch = match code_point(u32::from_str_radix(digits, 16).ok(), span) {
+            Ok(ch) => ch,
+            Err(tok) => return Some(tok),
+        };
+        if self.text_mode {
+            if let Some(tok) = self.text_token(ch) {
+                return Some(tok);
+            }
+        }
+        Some(self.structural_token(ch))
+    }
+
+    /// Handle the characters which have a different meaning in text mode.
+    ///
+    /// Returns `None` for the characters which behave the same as in math mode.
+    fn text_token(&mut self, ch: char) -> Option<Token<'static>> {
+        let tok = match ch {
+            '$' => Token::MathShift,
+            '-' => {
+                if self.peek.1 != '-' {
+                    Token::Letter('-')
+                } else {
+                    self.read_char();
+                    if self.peek.1 == '-' {
+                        self.read_char();
+                        Token::Letter('—')
+                    } else {
+                        Token::Letter('–')
+                    }
+                }
+            }
+            '`' | '\'' => {
+                let double = self.peek.1 == ch;
+                if double {
+                    self.read_char();
+                }
+                Token::Letter(match (ch, double) {
+                    ('`', false) => '‘',
+                    ('`', true) => '“',
+                    (_, false) => '’',
+                    (_, true) => '”',
+                })
+            }
+            _ => return None,
+        };
+        Some(tok)
+    }
+}
+
+/// Convert a code point to a character, or to the error token for `span`.
+fn code_point(code_point: Option<u32>, span: &str) -> Result<char, Token<'_>> {
+    match code_point {
+        Some(0xD800..=0xDFFF) => Err(Token::SurrogateCodePoint(span)),
+        Some(code_point) => match char::from_u32(code_point) {
+            Some(ch) if ch != '\u{0}' => Ok(ch),
+            _ => Err(Token::InvalidCodePoint(span)),
+        },
+        None => Err(Token::InvalidCodePoint(span)),
     }
 }
 
@@ -197,6 +478,16 @@ mod tests {
             ("space_and_number", r"\ 1", false),
             ("space_in_text", r"  x   y z", true),
             ("comment", "ab%hello\ncd", false),
+            ("unicode_symbols", "∑∫≤×⟨′″𝑥", false),
+            ("unicode_symbols_in_text", "≤×′", true),
+            ("ligatures_in_text", "a--b---c-d``e''f`g'", true),
+            ("accents_in_text", r"\'e\c c\i$x$", true),
+            (
+                "code_points",
+                r#"\unicode{x1D4AB}\unicode{8747}\char"2A01\char'53\char`\%"#,
+                false,
+            ),
+            ("caret_notation", r"^^^^2a01^^3d^^5f", false),
         ];
 
         for (name, problem, text_mode) in problems.into_iter() {
@@ -205,7 +496,7 @@ mod tests {
             // Call `lexer.next_token(false)` until we get `Token::EOF`.
             let mut tokens = String::new();
             if text_mode {
-                write!(tokens, "(text mode)\n").unwrap();
+                writeln!(tokens, "(text mode)").unwrap();
             }
             loop {
                 let tokloc = lexer.next_token();
@@ -213,7 +504,7 @@ mod tests {
                     break;
                 }
                 let TokLoc(loc, tok) = tokloc;
-                write!(tokens, "{}: {:?}\n", loc, tok).unwrap();
+                writeln!(tokens, "{}: {:?}", loc, tok).unwrap();
             }
             assert_snapshot!(name, &tokens, problem);
         }
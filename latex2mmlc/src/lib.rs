@@ -14,18 +14,31 @@
 //! - Parentheses, e.g., `\left\{ .. \middle| .. \right]`, ...
 //! - Integrals, e.g., `\int_0^\infty`, `\iint`, `\oint`, ...
 //! - Big operators, e.g., `\sum`, `\prod`, `\bigcup_{i = 0}^\infty`, ...
-//! - Limits and overset/underset, e.g., `\lim`, `\overset{}{}`, `\overbrace{}{}`, ...
-//! - Font styles, e.g. `\mathrm`, `\mathbf`, `\bm`, `\mathit`, `\mathsf`, `\mathscr`, `\mathbb`, `\mathfrak`, `\texttt`.
+//! - Limits and overset/underset, e.g., `\lim`, `\sum\nolimits`, `\operatorname*`, `\overset{}{}`, `\overbrace{}{}`, ...
+//! - Font styles, e.g. `\mathrm`, `\mathbf`, `\bm`, `\mathit`, `\mathsf`, `\mathscr`, `\mathcal`, `\mathbb`, `\mathfrak`, `\texttt`, `\textsf`, `\emph`.
 //!   - MathML lacks calligraphic mathvariant: https://github.com/mathml-refresh/mathml/issues/61
+//! - unicode-math alphabets, e.g. `\symbf`, `\symbfit`, `\symsf`, `\symup`, `\mathbfit`, `\mathsfbfit`.
+//! - Math style presets for the shapes of letters, see [`MathStyle`].
+//! - Right-to-left math and the Arabic math alphabets, e.g. `\mathinitial`, `\mathlooped`.
+//! - Font sizes, e.g. `\tiny`, `\small`, `\large`, `\Huge`.
+//! - Characters by code point: `\unicode{x1D4AB}`, `\char"2A01`, `^^^^2a01`.
 //! - White spaces, e.g., `\!`, `\,`, `\:`, `\;`, `\ `, `\quad`, `\qquad`.
 //! - Matrix, e.g. `\begin{matrix}`, `\begin{pmatrix}`, `\begin{bmatrix}`, `\begin{vmatrix}`.
+//! - Arrays with column specifications like `\begin{array}{l|cr}`, and `\multicolumn`, `\hdotsfor`, `\hline` and `\cline` in tables.
 //! - Multi-line equation `\begin{align}` (experimental).
+//! - Line breaks `\\`, `\newline` and `\allowbreak`; outside of an environment, the lines are stacked (aligned at `&`), or, with `Config::inline_line_breaks`, inline math is split into several `<math>` elements at them.
+//! - Optional line-break opportunities for long inline formulas (`Config::inline_line_breaks`).
+//! - Proof trees of bussproofs: `\begin{prooftree}` with `\AxiomC`, `\UnaryInfC`, `\BinaryInfC`, `\TrinaryInfC`, `\LeftLabel` and `\RightLabel`.
+//! - Set-builder notation and bra-kets of the braket package: `\set`, `\Set`, `\braket`, `\Braket`; the capitalized versions stretch the brackets and the bars.
+//! - Moving the index of a root with `\leftroot` and `\uproot`, e.g. `\sqrt[\leftroot{-2}\uproot{2}\beta]{k}`, and the radical sign `\surd`.
+//! - Limits with accents, `\varlimsup`, `\varliminf`, `\varinjlim`, `\varprojlim`, and `\injlim`, `\projlim`; further operators with limits like `\argmax` can be added with `Config::limit_operators`.
+//! - Text-mode commands like `\AA`, `\ss`, `\o`, `\c{c}` and `\textbackslash` are only accepted in text, math commands like `\alpha` only in math; `Config::lenient_modes` accepts both everywhere, with a warning from `latex_to_mathml_with_warnings`.
+//! - Locale-aware numbers with `Config::number_format`: the decimal and grouping separators, `3{,}14` and `1\,000`, and grouping long numbers with thin spaces.
+//! - Localized function names with `Config::operator_locale`, e.g. `\sen`, `\tg`, `\arcsen` in Spanish and `\sh`, `\ch`, `\th` in Russian and French; optionally also for `\sin`, `\tan`, ...
 //! - Feynman slash notation: `\slashed{\partial}`.
 //!
 //! ## Unsupported LaTeX commands
 //!
-//! - New line `\\`, except for ones in a matrix or align environment.
-//! - Alignment `&`, except for ones in a matrix or align environment.
 //! - Complicated sub/superscripts (`<mmultiscripts>`).
 //!
 //!
@@ -47,10 +60,13 @@
 //! and [`examples/document.rs`](https://github.com/osanshouo/latex2mathml/blob/master/examples/document.rs).
 //!
 use mathml_renderer::arena::Arena;
+use mathml_renderer::ast::{table_rows, Node};
+use mathml_renderer::attribute::{Align, FracAttr};
 
 pub(crate) mod commands;
 mod error;
 pub(crate) mod lexer;
+pub(crate) mod line_breaks;
 pub(crate) mod parse;
 pub(crate) mod predefined;
 pub mod token;
@@ -65,10 +81,134 @@ pub enum Display {
     Inline,
 }
 
+/// The letter shapes of a unicode-math `math-style` preset.
+///
+/// | Preset    | Latin lowercase | Latin uppercase | Greek lowercase | Greek uppercase |
+/// |-----------|-----------------|-----------------|-----------------|-----------------|
+/// | `TeX`     | italic          | italic          | italic          | upright         |
+/// | `Iso`     | italic          | italic          | italic          | italic          |
+/// | `French`  | italic          | upright         | upright         | upright         |
+/// | `Upright` | upright         | upright         | upright         | upright         |
+///
+/// The preset also selects the `bold-style` of `\symbf`: bold letters are italic
+/// with `Iso`, upright with `French` and `Upright`, and follow TeX with `TeX`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MathStyle {
+    #[default]
+    TeX,
+    Iso,
+    French,
+    Upright,
+}
+
+/// The language of the names of functions like sine and tangent.
+///
+/// Each language adds the commands for its names, like `\sen` and `\tg` in Spanish
+/// or `\sh` and `\th` in Russian and French. With `Config::localize_standard_operators`,
+/// the standard commands like `\sin` and `\tan` are displayed with these names too.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OperatorLocale {
+    #[default]
+    English,
+    French,
+    Russian,
+    Spanish,
+}
+
+/// How digits and separators are combined into numbers.
+///
+/// The separators can be `.` or `,`. Between two digits, the decimal separator and
+/// the grouping separator continue the number; any other separator ends it. For
+/// example, with `,` as the decimal separator and no grouping separator, `1,2` is a
+/// single number, while with the default format `f(1,2)` still is `1,2`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NumberFormat {
+    /// The separator between the integer part and the fractional part.
+    pub decimal_separator: char,
+    /// The separator between groups of digits, like the `,` in `1,000`.
+    pub group_separator: Option<char>,
+    /// Whether a separator in braces continues the number, as in `3{,}14`.
+    pub braced_separators: bool,
+    /// Whether a thin space `\,` between digits continues the number, as in `1\,000`.
+    pub thin_space_groups: bool,
+    /// Group the digits of numbers with five digits or more in threes with thin spaces
+    /// on output, replacing the grouping separators of the input.
+    pub group_digits: bool,
+}
+
+impl NumberFormat {
+    /// Whether `ch` is the decimal separator or the grouping separator.
+    pub(crate) fn is_separator(&self, ch: char) -> bool {
+        ch == self.decimal_separator || Some(ch) == self.group_separator
+    }
+}
+
+impl Default for NumberFormat {
+    fn default() -> Self {
+        NumberFormat {
+            decimal_separator: '.',
+            group_separator: Some(','),
+            braced_separators: false,
+            thin_space_groups: false,
+            group_digits: false,
+        }
+    }
+}
+
+/// Options for [`latex_to_mathml_with_config`].
+#[derive(Debug, Clone, Default)]
+pub struct Config {
+    /// Split inline formulas into several `<math>` elements after top-level relations
+    /// and binary operators, so that browsers can break long formulas across lines.
+    /// The formula is also split at `\\`, `\newline` and `\allowbreak`, instead of
+    /// stacking the lines.
+    pub inline_line_breaks: bool,
+    /// Render `\mathcal` and `\mathscr` as the same plain script letters, without the
+    /// variation selectors U+FE00 and U+FE01, for fonts that lack the two variants.
+    pub plain_script: bool,
+    /// The preset for the shapes of single letters.
+    pub math_style: MathStyle,
+    /// Render the differential d and the constants e, i and π upright, as ISO 80000-2
+    /// asks for them.
+    ///
+    /// A `d` counts as a differential after `\,` and at the start of the numerator or
+    /// the denominator of a fraction, as in `\int f(x)\,dx` and `\frac{dy}{dx}`.
+    /// The constants can't be told apart from variables of the same name, so every
+    /// e, i and π is upright.
+    pub upright_constants: bool,
+    /// Lay out the math from right to left with `<math dir="rtl">`, as in Arabic texts.
+    ///
+    /// The characters are emitted as they are; the browser mirrors them with the
+    /// `rtlm` feature of the OpenType math font.
+    pub right_to_left: bool,
+    /// Additional command names, without the backslash, for operators with limits
+    /// like `\argmax`. The limits go below the name in display style and to its side
+    /// in inline style; the name itself is displayed as given.
+    pub limit_operators: Vec<String>,
+    /// Accept text-mode commands like `\AA` in math and math-mode commands like
+    /// `\alpha` in text, as earlier versions did, instead of reporting an error.
+    /// [`latex_to_mathml_with_warnings`] returns a warning for each of them.
+    pub lenient_modes: bool,
+    /// How digits and separators are combined into numbers.
+    pub number_format: NumberFormat,
+    /// The language of the function names, see [`OperatorLocale`].
+    pub operator_locale: OperatorLocale,
+    /// Display the standard functions like `\sin` and `\tan` with the names of
+    /// `operator_locale`, like "sen" and "tg" in Spanish.
+    pub localize_standard_operators: bool,
+}
+
 fn get_nodes<'arena, 'source>(
     latex: &'source str,
     arena: &'arena Arena,
-) -> Result<&'arena [&'arena mathml_renderer::ast::Node<'arena>], error::LatexError<'source>>
+    config: &'arena Config,
+) -> Result<
+    (
+        &'arena [&'arena Node<'arena>],
+        Vec<error::LatexError<'source>>,
+    ),
+    error::LatexError<'source>,
+>
 where
     'source: 'arena, // 'source outlives 'arena
 {
@@ -76,10 +216,16 @@ where
     // the string buffer.
     // let buffer = Buffer::new(latex.len());
 
-    let l = lexer::Lexer::new(latex);
-    let mut p = parse::Parser::new(l, arena);
+    let mut l = lexer::Lexer::new(latex);
+    l.lenient_modes = config.lenient_modes;
+    l.operator_locale = config.operator_locale;
+    l.localize_standard_operators = config.localize_standard_operators;
+    let mut p = parse::Parser::new(l, arena)
+        .with_letter_shapes(config.math_style, config.upright_constants)
+        .with_limit_operators(&config.limit_operators)
+        .with_number_format(config.number_format);
     let nodes = p.parse()?;
-    Ok(nodes)
+    Ok((nodes, p.into_warnings()))
 }
 
 /// Convert LaTeX text to MathML.
@@ -106,38 +252,167 @@ pub fn latex_to_mathml<'source, 'emitter>(
 where
     'source: 'emitter,
 {
+    latex_to_mathml_with_config(latex, display, pretty, &Config::default())
+}
+
+/// Convert LaTeX text to MathML, with the options given in `config`.
+///
+/// ```rust
+/// use latex2mmlc::{latex_to_mathml_with_config, Config, Display};
+///
+/// let config = Config {
+///     inline_line_breaks: true,
+///     ..Default::default()
+/// };
+/// let latex = r#"a + b = c"#;
+/// let mathml = latex_to_mathml_with_config(latex, Display::Inline, false, &config).unwrap();
+/// assert_eq!(
+///     mathml,
+///     "<math><mi>a</mi><mo>+</mo></math><math><mi>b</mi><mo>=</mo></math><math><mi>c</mi></math>"
+/// );
+/// ```
+pub fn latex_to_mathml_with_config<'source>(
+    latex: &'source str,
+    display: Display,
+    pretty: bool,
+    config: &Config,
+) -> Result<String, error::LatexError<'source>> {
+    latex_to_mathml_with_warnings(latex, display, pretty, config).map(|(mathml, _)| mathml)
+}
+
+/// Convert LaTeX text to MathML like [`latex_to_mathml_with_config`], and also return
+/// the warnings.
+///
+/// With `Config::lenient_modes`, a command in the wrong mode, like `\AA` in math, is
+/// accepted; there is a warning for it with the error it gives otherwise.
+///
+/// ```rust
+/// use latex2mmlc::{latex_to_mathml_with_warnings, Config, Display, LatexErrKind};
+///
+/// let config = Config {
+///     lenient_modes: true,
+///     ..Default::default()
+/// };
+/// let (_, warnings) =
+///     latex_to_mathml_with_warnings(r"x + \AA", Display::Inline, false, &config).unwrap();
+/// assert!(matches!(warnings[..], [latex2mmlc::LatexError(4, LatexErrKind::TextOnlyCommand("AA"))]));
+/// ```
+pub fn latex_to_mathml_with_warnings<'source>(
+    latex: &'source str,
+    display: Display,
+    pretty: bool,
+    config: &Config,
+) -> Result<(String, Vec<error::LatexError<'source>>), error::LatexError<'source>> {
     let arena = Arena::new();
-    let nodes = get_nodes(latex, &arena)?;
+    let (nodes, warnings) = get_nodes(latex, &arena, config)?;
 
     let mut output = MathMLEmitter::new();
+    if config.plain_script {
+        output.use_plain_script();
+    }
+    let base_indent = if pretty { 1 } else { 0 };
     match display {
-        Display::Block => output.push_str("<math display=\"block\">"),
-        Display::Inline => output.push_str("<math>"),
+        Display::Block => {
+            open_math(&mut output, " display=\"block\"", config);
+            emit_lines(
+                &mut output,
+                &arena,
+                nodes,
+                Some(FracAttr::DisplayStyleTrue),
+                base_indent,
+            );
+        }
+        Display::Inline if config.inline_line_breaks => {
+            let chunks = line_breaks::split_at_break_points(nodes);
+            for (i, chunk) in chunks.into_iter().enumerate() {
+                if i > 0 {
+                    close_math(&mut output, pretty);
+                }
+                open_math(&mut output, "", config);
+                emit_nodes(&mut output, chunk, base_indent);
+            }
+        }
+        Display::Inline => {
+            open_math(&mut output, "", config);
+            emit_lines(&mut output, &arena, nodes, None, base_indent);
+        }
     };
+    close_math(&mut output, pretty);
+    Ok((output.into_inner(), warnings))
+}
 
-    let base_indent = if pretty { 1 } else { 0 };
+/// Emit the top-level nodes, stacking the lines like `gathered` (or `aligned` if there
+/// is an `&`) if there are line breaks or alignment outside of an environment.
+fn emit_lines<'arena>(
+    output: &mut MathMLEmitter<'arena>,
+    arena: &'arena Arena,
+    nodes: &'arena [&'arena Node<'arena>],
+    attr: Option<FracAttr>,
+    base_indent: usize,
+) {
+    let has_new_line = nodes.iter().any(|node| matches!(node, Node::RowSeparator));
+    let has_alignment = nodes
+        .iter()
+        .any(|node| matches!(node, Node::ColumnSeparator));
+    if has_new_line || has_alignment {
+        let align = if has_alignment {
+            Align::Alternating
+        } else {
+            Align::Center
+        };
+        let table = arena.push(Node::Table {
+            rows: table_rows(arena, nodes, &[]),
+            align,
+            attr,
+        });
+        output.emit(table, base_indent);
+    } else {
+        emit_nodes(output, nodes, base_indent);
+    }
+}
+
+fn emit_nodes<'arena>(
+    output: &mut MathMLEmitter<'arena>,
+    nodes: &[&'arena Node<'arena>],
+    base_indent: usize,
+) {
     for node in nodes.iter() {
         output.emit(node, base_indent);
     }
+}
+
+fn open_math(output: &mut MathMLEmitter, display: &str, config: &Config) {
+    output.push_str("<math");
+    output.push_str(display);
+    if config.right_to_left {
+        output.push_str(" dir=\"rtl\"");
+    }
+    output.push('>');
+}
+
+fn close_math(output: &mut MathMLEmitter, pretty: bool) {
     if pretty {
         output.push('\n');
     }
     output.push_str("</math>");
-    Ok(output.into_inner())
 }
 
 #[cfg(test)]
 mod tests {
     use insta::assert_snapshot;
 
-    use crate::{error, latex_to_mathml, LatexError};
+    use crate::{
+        error, latex_to_mathml, latex_to_mathml_with_config, latex_to_mathml_with_warnings, Config,
+        LatexErrKind, LatexError, MathStyle, NumberFormat, OperatorLocale,
+    };
     use mathml_renderer::ast::MathMLEmitter;
 
     use super::{get_nodes, Arena};
 
-    fn convert_content(latex: &str) -> Result<String, error::LatexError> {
+    fn convert_content(latex: &str) -> Result<String, error::LatexError<'_>> {
         let arena = Arena::new();
-        let nodes = get_nodes(latex, &arena)?;
+        let config = Config::default();
+        let (nodes, _) = get_nodes(latex, &arena, &config)?;
         let mut emitter = MathMLEmitter::new();
         for node in nodes.iter() {
             emitter.emit(node, 0);
@@ -159,6 +434,7 @@ mod tests {
             ("text_then_space", r"\text{x}~y"),
             ("text_nested", r"\text{ \text{a}}"),
             ("text_rq", r"\text{\rq}"),
+            ("text_yen", r"\text{\textyen 100}"),
             ("text_with_escape_brace", r"\text{a\}b}"),
             ("text_with_weird_o", r"\text{x\o y}"),
             ("text_with_group", r"\text{x{y}z{}p{}}"),
@@ -168,6 +444,21 @@ mod tests {
             ("textbf_with_digit_dot", r"\textbf{1234.}"),
             ("textbf_with_digit_decimal", r"\textbf{1234.5}"),
             ("texttt", r"\texttt{x}"),
+            ("textrm", r"\textrm{x}"),
+            ("textsf", r"\textsf{x}"),
+            ("textnormal", r"\textbf{a \textnormal{b}}"),
+            ("textup", r"\textit{a \textup{b}}"),
+            ("textsl", r"\textsl{x}"),
+            ("emph", r"\emph{x}"),
+            ("emph_nested", r"\textit{a \emph{b} c}"),
+            ("mbox", r"\mbox{if } x"),
+            ("hbox", r"\hbox{x y}"),
+            ("textbf_textit", r"\textbf{\textit{x}}"),
+            ("textit_textbf", r"\textit{\textbf{x}}"),
+            ("textsf_textbf_textit", r"\textsf{\textbf{\textit{x}}}"),
+            ("textsf_textrm", r"\textsf{a \textrm{b}}"),
+            ("textbf_nested_same_font", r"\textbf{a \textbf{b}}"),
+            ("textbf_with_math", r"\textbf{a $x$}"),
             ("mathtt", r"\mathtt{x}"),
             ("mathtt_with_digit", r"\mathtt2"),
             ("mathbf_with_digit", r"\mathbf{1234}"),
@@ -193,6 +484,11 @@ mod tests {
             ("square_root_with_space", r"\sqrt 12"),
             ("complex_square_root", r"\sqrt{x+2}"),
             ("cube_root", r"\sqrt[3]{x}"),
+            ("shifted_root", r"\sqrt[\leftroot{-2}\uproot{2}\beta]{k}"),
+            (
+                "far_shifted_root",
+                r"\sqrt[\leftroot{200}\uproot{-200}n]{x}",
+            ),
             ("simple_fraction", r"\frac{1}{2}"),
             ("fraction_without_space", r"\frac12"),
             ("fraction_with_space", r"\frac 12"),
@@ -223,9 +519,49 @@ mod tests {
             ("integral_pointless_limits", r"\int\limits dx"),
             ("bold_font", r"\bm{x}"),
             ("black_board_font", r"\mathbb{R}"),
+            (
+                "mathcal_and_mathscr",
+                r"\mathcal{L} \mathscr{L} \mathcal{l}",
+            ),
+            ("mathbfcal_and_mathbfscr", r"\mathbfcal{L} \mathbfscr{L}"),
+            (
+                "symbf_bold_style",
+                r"\symbf{x\alpha\Gamma} \symbfit{x\Gamma} \symbfup{\alpha}",
+            ),
+            ("symbfsf_bold_style", r"\symbfsf{A\beta}"),
+            (
+                "combined_math_alphabets",
+                r"\mathbfit{x} \mathsfbfit{y} \mathbfscr{L} \mathbffrak{g}",
+            ),
+            ("symnormal_resets", r"\mathbf{a\symnormal{b\Gamma}}"),
+            ("symup_and_symit", r"\symup{x} \symit{\Gamma}"),
             ("sum_with_special_symbol", r"\sum_{i = 0}^∞ i"),
             ("sum_with_limit", r"\sum\limits_{i=1}^N"),
             ("sum_pointless_limits", r"\sum\limits n"),
+            ("sum_nolimits", r"\sum\nolimits_{i=1}^N"),
+            ("sum_last_limits_wins", r"\sum\nolimits\limits_{i=1}^N"),
+            ("integral_displaylimits", r"\int\displaylimits_0^1"),
+            ("lim_nolimits", r"\lim\nolimits_{x \to 0} f"),
+            ("lim_with_superscript", r"\limsup_{n}^{*}"),
+            (
+                "varlimsup_varliminf",
+                r"\varlimsup_{n \to \infty} a_n \le \varliminf_{n \to \infty} b_n",
+            ),
+            (
+                "varinjlim_varprojlim",
+                r"\varinjlim_{i} A_i \to \varprojlim_{j} B_j",
+            ),
+            ("injlim_projlim", r"\injlim_{i} A_i, \projlim_{j} B_j"),
+            ("varlimsup_nolimits", r"\varlimsup\nolimits_{n} a_n"),
+            ("max_limits", r"\max\limits_{x}"),
+            ("operatorname_star", r"\operatorname*{argmax}_{x} f"),
+            (
+                "operatorname_star_limits",
+                r"\operatorname*{argmax}\limits_{x} f",
+            ),
+            ("lim_displaylimits", r"\lim\displaylimits_{x \to 0} f"),
+            ("operatorname_limits", r"\operatorname{sgn}\limits_0"),
+            ("operatorname_subscript", r"\operatorname{sgn}_0"),
             ("product", r"\prod_n n"),
             ("underscore", r"x\ y"),
             ("stretchy_brace", r"\left\{ x  ( x + 2 ) \right\}"),
@@ -236,6 +572,40 @@ mod tests {
                 r#"\begin{align} f ( x ) &= x^2 + 2 x + 1 \\ &= ( x + 1 )^2\end{align}"#,
             ),
             ("align_star", r#"\begin{align*}x&=1\\y=2\end{align*}"#),
+            (
+                "array_column_spec",
+                r"\begin{array}{|l|cr|} a & b & c \\ 10 & 20 & 30 \end{array}",
+            ),
+            (
+                "multicolumn",
+                r"\begin{array}{ccc} a & b & c \\ \multicolumn{2}{|l}{x + y} & z \end{array}",
+            ),
+            (
+                "hdotsfor",
+                r"\begin{pmatrix} a_{11} & \hdotsfor{2} & a_{1n} \\ a_{21} & a_{22} & \hdotsfor[2]{2} \end{pmatrix}",
+            ),
+            ("set", r"\set{x \in X | x > 0}"),
+            (
+                "stretched_set",
+                r"\Set{\frac{1}{n} | n \in \mathbb{N}, |n| > 1}",
+            ),
+            ("braket", r"\braket{\phi | A \| \psi}"),
+            (
+                "stretched_braket",
+                r"\Braket{\phi | \frac{\partial}{\partial t} | \psi}",
+            ),
+            (
+                "prooftree",
+                r"\begin{prooftree} \AxiomC{$A$} \AxiomC{$B$} \RightLabel{$\land$I} \BinaryInfC{$A \land B$} \LeftLabel{(E)} \UnaryInfC{$A$} \end{prooftree}",
+            ),
+            (
+                "prooftree_nested",
+                r"\begin{prooftree} \AxiomC{$[A]$} \UnaryInfC{$B$} \AxiomC{$C$} \AxiomC{$D$} \TrinaryInfC{$E$} \end{prooftree}",
+            ),
+            (
+                "hline_and_cline",
+                r"\begin{array}{cc} \hline a & b \\ \cline{2-2} c & d \\ \hline \end{array}",
+            ),
             (
                 "text_transforms",
                 r#"{fi}\ \mathit{fi}\ \mathrm{fi}\ \texttt{fi}"#,
@@ -256,9 +626,18 @@ mod tests {
                 r"\sum_{\genfrac{}{}{0pt}{}{\scriptstyle 0 \le i \le m}{\scriptstyle 0 < j < n}} P(i, j)",
             ),
             ("genfrac", r"\genfrac(]{0pt}{2}{a+b}{c+d}"),
+            ("genfrac_thickness", r"\genfrac{}{}{1.5pt}{}{a}{b}"),
             ("not_subset", r"\not\subset"),
             ("not_less_than", r"\not\lt"),
             ("not_less_than_symbol", r"\not< x"),
+            ("not_cong", r"\not\cong"),
+            ("not_sim", r"\not\sim"),
+            ("not_parallel", r"\not\parallel"),
+            ("not_ni", r"\not\ni"),
+            ("not_without_precomposed", r"\not\approxeq"),
+            ("not_equals_then_minus", r"x \not= -1"),
+            ("not_text", r"a \not\text{R} b"),
+            ("not_text_bold", r"\not\textbf{is} x"),
             ("mathrm_with_superscript", r"\mathrm{x}^2"),
             ("mathrm_with_sin", r"\mathrm{x\sin}"),
             ("mathrm_with_sin2", r"\mathrm{\sin x}"),
@@ -273,12 +652,31 @@ mod tests {
             ("mathrm_nested3", r"\mathrm{ab\mathit{cd}ef}"),
             ("mathrm_nested4", r"\mathit{\mathrm{a}}"),
             ("mathrm_multiletter", r"\mathrm{abc}"),
-            ("complicated_operatorname", r"\operatorname {{\pi} o \o a}"),
+            (
+                "complicated_operatorname",
+                r"\operatorname {{\pi} o \text{\o} a}",
+            ),
             (
                 "continued_fraction",
                 r"a_0 + \cfrac{1}{a_1 + \cfrac{1}{a_2 + \cfrac{1}{a_3 + \cfrac{1}{a_4}}}}",
             ),
             ("standalone_underscore", "_2F_3"),
+            ("over", r"{a \over b}"),
+            ("over_top_level", r"x + 1 \over 2"),
+            ("over_with_scripts", r"{a^2 \over b_1} + c"),
+            ("over_twice", r"{a \over b \over c}"),
+            ("over_nested", r"{{a \over b} \over c}"),
+            (
+                "over_in_matrix",
+                r"\begin{matrix}a \over b & c \\ d & e \over f\end{matrix}",
+            ),
+            ("atop", r"{a \atop b}"),
+            ("above", r"{a \above 2pt b}"),
+            ("above_decimal", r"{a \above 0.4pt b}"),
+            ("above_em", r"a \above 0.1em b"),
+            ("choose", r"{n \choose k}"),
+            ("brace", r"{n \brace k}"),
+            ("brack", r"{n \brack k}"),
             ("prime", r"f'"),
             ("double_prime", r"f''"),
             ("triple_prime", r"f'''"),
@@ -292,6 +690,32 @@ mod tests {
             ("double_prime_and_sub_super", r"f''_3^2"),
             ("sum_prime", r"\sum'"),
             ("int_prime", r"\int'"),
+            ("unicode_relations", "α ≤ β → γ ∈ Δ"),
+            ("unicode_sum", "∑_{n=1}^∞ a_n"),
+            ("unicode_integral", "∫_0^1 f(x) dx"),
+            ("unicode_binary_op", "a × b ± c"),
+            ("unicode_delimiters", "⟨x, y⟩ ‖v‖"),
+            ("unicode_math_alphanumeric", "𝑥 + 𝐀"),
+            ("unicode_primes", "f″ + g′ + h‴ + k′′"),
+            ("unicode_in_text", r"\text{α ≤ β}"),
+            ("text_with_math", r"\text{if $x>0$}"),
+            (
+                "text_with_paren_math",
+                r"\text{for all \(n \in \mathbb{N}\) and}",
+            ),
+            ("text_with_math_then_relation", r"\text{$a$}≤ b"),
+            (
+                "text_with_nested_text_in_math",
+                r"\text{a $\text{b $c$}$ d}",
+            ),
+            (
+                "text_accents",
+                r#"\text{caf\'e na\"ive gar\c{c}on \^a\`u \v{s}}"#,
+            ),
+            ("text_accent_dotless_i", r#"\text{na\"\i ve}"#),
+            ("text_accent_not_precomposed", r#"\text{\"{q}}"#),
+            ("text_dashes", r"\text{1--2 --- a-b}"),
+            ("text_quotes", r"\text{``quoted'' `single' it's}"),
             ("vec_prime", r"\vec{x}'"),
             ("overset_with_prime", r"\overset{!}{=}'"),
             ("overset_prime", r"\overset{'}{=}"),
@@ -318,15 +742,312 @@ mod tests {
             ("xrightarrow", r"\xrightarrow{x}"),
             ("slashed", r"\slashed{\partial}"),
             ("plus_after_equal", r"x = +4"),
+            ("mathring", r"\mathring{A}"),
+            ("dddot_ddddot", r"\dddot{x} \ddddot{y}"),
+            ("semantic_dots", r"\dotsc \dotsb \dotsm \dotsi \dotso"),
+            ("dots_before_comma", r"x_1, \dots, x_n"),
+            ("dots_before_binary_op", r"x_1 + \dots + x_n"),
+            ("dots_before_relation", r"x_1 = \dots = x_n"),
+            ("dots_before_integral", r"\int \dots \int"),
+            ("dots_at_end", r"(x_1 \dots)"),
+            ("font_size", r"a {\large b} \Huge c"),
+            ("unicode_code_point", r"\unicode{x1D4AB} \unicode{ 8805 } x"),
+            ("char_command", r#"a \char"2A01 b \char`\% \char 49"#),
+            ("caret_notation", r"x ^^^^2264 y^^5e2"),
+            ("char_lowercase_hex", r#"\char"41a"#),
+            ("code_point_in_text", r#"\text{\char"E9t\unicode{x1F600}}"#),
+            ("top_level_newline", r"a = b \\ c = d"),
+            ("top_level_newline_command", r"x \newline y"),
+            ("allowbreak", r"a + b = \allowbreak c + d"),
+            ("allowbreak_in_group", r"{a + \allowbreak b} \newline c"),
+            ("font_size_nested", r"{\tiny x {\normalsize y}} z"),
         ];
 
         for (name, problem) in problems.into_iter() {
             let mathml = latex_to_mathml(problem, crate::Display::Inline, true)
-                .expect(format!("failed to convert `{}`", problem).as_str());
+                .unwrap_or_else(|_| panic!("failed to convert `{}`", problem));
+            assert_snapshot!(name, &mathml, problem);
+        }
+    }
+
+    #[test]
+    fn display_block_test() {
+        let problems = [
+            ("block_without_newline", r"x = 1"),
+            ("block_newline", r"a + b \\ c"),
+            ("block_newline_alignment", r"a &= b \\ &= c"),
+            ("block_newline_in_group", r"\frac{a}{b \\ c}"),
+        ];
+
+        for (name, problem) in problems.into_iter() {
+            let mathml = latex_to_mathml(problem, crate::Display::Block, true)
+                .unwrap_or_else(|_| panic!("failed to convert `{}`", problem));
+            assert_snapshot!(name, &mathml, problem);
+        }
+    }
+
+    #[test]
+    fn inline_line_breaks_test() {
+        let problems = [
+            ("break_after_relation", r"x^2 = y \le z"),
+            ("break_after_binary_op", r"a + b \cdot c - d"),
+            ("no_break_after_prefix_sign", r"a = -b"),
+            ("no_break_inside_group", r"{a + b} + \frac{c + d}{2}"),
+            ("no_break_at_end", r"a +"),
+            ("break_at_allowbreak", r"f(x,\allowbreak y)"),
+            ("break_at_newline", r"a \\ b \newline c"),
+            ("no_break_after_quantifier", r"\forall x"),
+        ];
+
+        let config = Config {
+            inline_line_breaks: true,
+            ..Default::default()
+        };
+        for (name, problem) in problems.into_iter() {
+            let mathml =
+                latex_to_mathml_with_config(problem, crate::Display::Inline, true, &config)
+                    .unwrap_or_else(|_| panic!("failed to convert `{}`", problem));
+            assert_snapshot!(name, &mathml, problem);
+        }
+    }
+
+    #[test]
+    fn upright_differential_test() {
+        let config = Config {
+            upright_constants: true,
+            ..Default::default()
+        };
+        let convert = |latex| {
+            latex_to_mathml_with_config(latex, crate::Display::Inline, false, &config).unwrap()
+        };
+        assert_eq!(
+            convert(r"\frac{d}{dx}"),
+            r#"<math><mfrac><mi mathvariant="normal">d</mi><mrow><mi mathvariant="normal">d</mi><mi>x</mi></mrow></mfrac></math>"#
+        );
+        assert_eq!(
+            convert(r"f\,dx"),
+            r#"<math><mi>f</mi><mspace width="0.1667em"/><mi mathvariant="normal">d</mi><mi>x</mi></math>"#
+        );
+        assert_eq!(
+            convert(r"a + d"),
+            "<math><mi>a</mi><mo>+</mo><mi>d</mi></math>"
+        );
+        assert_eq!(
+            convert(r"e"),
+            r#"<math><mi mathvariant="normal">e</mi></math>"#
+        );
+    }
+
+    #[test]
+    fn plain_script_test() {
+        let config = Config {
+            plain_script: true,
+            ..Default::default()
+        };
+        let convert = |latex| {
+            latex_to_mathml_with_config(latex, crate::Display::Inline, false, &config).unwrap()
+        };
+        assert_eq!(convert(r"\mathcal{L}"), "<math><mi>ℒ</mi></math>");
+        assert_eq!(convert(r"\mathscr{L}"), "<math><mi>ℒ</mi></math>");
+        assert_eq!(convert(r"\mathbfscr{L}"), "<math><mi>𝓛</mi></math>");
+    }
+
+    #[test]
+    fn math_style_test() {
+        let problems = [
+            ("math_style_tex", MathStyle::TeX, false),
+            ("math_style_iso", MathStyle::Iso, false),
+            ("math_style_french", MathStyle::French, false),
+            ("math_style_upright", MathStyle::Upright, false),
+            ("math_style_iso_upright_constants", MathStyle::Iso, true),
+        ];
+        let latex = r"a B \alpha \Gamma \varGamma \symbf{v\Delta} \int e^{i\pi x} \,dx";
+
+        for (name, math_style, upright_constants) in problems.into_iter() {
+            let config = Config {
+                math_style,
+                upright_constants,
+                ..Default::default()
+            };
+            let mathml = latex_to_mathml_with_config(latex, crate::Display::Inline, true, &config)
+                .unwrap_or_else(|_| panic!("failed to convert `{}`", latex));
+            assert_snapshot!(name, &mathml, latex);
+        }
+    }
+
+    #[test]
+    fn right_to_left_test() {
+        let problems = [
+            (
+                "rtl_arrows_and_fences",
+                r"f\colon A \to B, \left( x \Rightarrow y \right]",
+            ),
+            ("rtl_slash", r"a / b \setminus c"),
+            (
+                "rtl_arabic_alphabets",
+                r"\mathinitial{ب} \mathtailed{ج} \mathstretched{ق} \mathlooped{و} \mathbb{د}",
+            ),
+        ];
+        let config = Config {
+            right_to_left: true,
+            ..Default::default()
+        };
+        for (name, problem) in problems.into_iter() {
+            let mathml = latex_to_mathml_with_config(problem, crate::Display::Block, true, &config)
+                .unwrap_or_else(|_| panic!("failed to convert `{}`", problem));
+            assert_snapshot!(name, &mathml, problem);
+        }
+    }
+
+    #[test]
+    fn limit_operators_test() {
+        let problems = [
+            (
+                "limit_operators_display",
+                r"\argmax_{x \in X} f(x) = \esssup\limits_{y} g",
+                crate::Display::Block,
+            ),
+            (
+                "limit_operators_inline",
+                r"\argmin_{x} f(x), \argmin\nolimits_{x} g(x)",
+                crate::Display::Inline,
+            ),
+        ];
+        let config = Config {
+            limit_operators: vec!["argmax".into(), "argmin".into(), "esssup".into()],
+            ..Default::default()
+        };
+        for (name, problem, display) in problems.into_iter() {
+            let mathml = latex_to_mathml_with_config(problem, display, true, &config)
+                .unwrap_or_else(|_| panic!("failed to convert `{}`", problem));
+            assert_snapshot!(name, &mathml, problem);
+        }
+        // Without the configuration, the names stay unknown.
+        assert!(latex_to_mathml(r"\argmax_x f", crate::Display::Block, false).is_err());
+    }
+
+    #[test]
+    fn lenient_modes_test() {
+        let problems = [
+            ("lenient_text_command_in_math", r"\AA + \o"),
+            ("lenient_math_command_in_text", r"\text{\alpha = \beta}"),
+        ];
+        let config = Config {
+            lenient_modes: true,
+            ..Default::default()
+        };
+        for (name, problem) in problems.into_iter() {
+            let mathml =
+                latex_to_mathml_with_config(problem, crate::Display::Inline, true, &config)
+                    .unwrap_or_else(|_| panic!("failed to convert `{}`", problem));
+            assert_snapshot!(name, &mathml, problem);
+        }
+        // Each command in the wrong mode gives a warning.
+        let latex = r"\AA + \text{\alpha}";
+        let (_, warnings) =
+            latex_to_mathml_with_warnings(latex, crate::Display::Inline, false, &config).unwrap();
+        assert!(matches!(
+            warnings[..],
+            [
+                LatexError(0, LatexErrKind::TextOnlyCommand("AA")),
+                LatexError(12, LatexErrKind::MathOnlyCommand("alpha")),
+            ]
+        ));
+    }
+
+    #[test]
+    fn number_format_test() {
+        let german = NumberFormat {
+            decimal_separator: ',',
+            group_separator: Some('.'),
+            braced_separators: true,
+            thin_space_groups: true,
+            group_digits: false,
+        };
+        let problems = [
+            (
+                "number_format_default",
+                r"f(1,2) = 3{,}14 + 1\,000",
+                NumberFormat::default(),
+            ),
+            (
+                "number_format_german",
+                r"3{,}14 + 1\,000 + 1.000,5 + f(1{.}2)",
+                german,
+            ),
+            (
+                "number_format_no_grouping",
+                r"f(1,2) = 1.5",
+                NumberFormat {
+                    group_separator: None,
+                    ..Default::default()
+                },
+            ),
+            (
+                "number_format_group_digits",
+                r"12345678.123456 + 1,000,000 + 1\,234 + 9999",
+                NumberFormat {
+                    thin_space_groups: true,
+                    group_digits: true,
+                    ..Default::default()
+                },
+            ),
+        ];
+        for (name, problem, number_format) in problems.into_iter() {
+            let config = Config {
+                number_format,
+                ..Default::default()
+            };
+            let mathml =
+                latex_to_mathml_with_config(problem, crate::Display::Inline, true, &config)
+                    .unwrap_or_else(|_| panic!("failed to convert `{}`", problem));
             assert_snapshot!(name, &mathml, problem);
         }
     }
 
+    #[test]
+    fn operator_locale_test() {
+        let problems = [
+            (
+                "operator_locale_spanish",
+                r"\sen x + \tg y = \sin x + \arcsen z",
+                OperatorLocale::Spanish,
+                false,
+            ),
+            (
+                "operator_locale_spanish_renamed",
+                r"\sin x + \tan y + \arcsin z",
+                OperatorLocale::Spanish,
+                true,
+            ),
+            (
+                "operator_locale_russian",
+                r"\th x = \sh x / \ch x, \text{\th}",
+                OperatorLocale::Russian,
+                false,
+            ),
+            (
+                "operator_locale_french_renamed",
+                r"\sinh x + \cot y + \sin z",
+                OperatorLocale::French,
+                true,
+            ),
+        ];
+        for (name, problem, operator_locale, localize_standard_operators) in problems.into_iter() {
+            let config = Config {
+                operator_locale,
+                localize_standard_operators,
+                ..Default::default()
+            };
+            let mathml =
+                latex_to_mathml_with_config(problem, crate::Display::Inline, true, &config)
+                    .unwrap_or_else(|_| panic!("failed to convert `{}`", problem));
+            assert_snapshot!(name, &mathml, problem);
+        }
+        // Without a locale, the localized names stay unknown.
+        assert!(latex_to_mathml(r"\sen x", crate::Display::Inline, false).is_err());
+    }
+
     #[test]
     fn error_test() {
         let problems = [
@@ -339,6 +1060,8 @@ mod tests {
             ),
             ("incorrect_bracket", r"\operatorname[lim}"),
             ("unclosed_bracket", r"\sqrt[lim"),
+            ("uproot_outside_root", r"\uproot{2} x"),
+            ("invalid_root_shift", r"\sqrt[\leftroot{2pt}n]{x}"),
             ("mismatched_begin_end", r"\begin{matrix} 1 \end{bmatrix}"),
             (
                 "spaces_in_env_name",
@@ -360,14 +1083,67 @@ mod tests {
             ("unclosed_env", r"\begin{matrix} x"),
             ("unclosed_text", r"\text{hello"),
             ("unexpected_limits", r"\text{hello}\limits_0^1"),
-            ("unsupported_not", r"\not\text{hello}"),
+            ("unexpected_nolimits", r"x\nolimits_0"),
+            ("unsupported_not", r"\not\sqrt{2}"),
+            ("not_empty_text", r"\not\text{} a"),
+            ("not_empty_bold_text", r"\not\textbf{}"),
             ("operatorname_with_other_operator", r"x\operatorname{\max}"),
             ("text_with_unclosed_group", r"\text{x{}"),
+            ("text_with_unclosed_math", r"\text{a $x}"),
+            ("inline_math_outside_text", r"\(x\)"),
+            ("over_as_argument", r"\frac\over b"),
+            ("above_unsupported_length", r"{a \above 0.5bp b}"),
             ("super_then_prime", "f^2'"),
             ("sub_super_then_prime", "f_5^2'"),
             ("sup_sup", "x^2^3 y"),
             ("sub_sub", "x_2_3 y"),
+            ("unicode_out_of_range", r"\unicode{x110000}"),
+            ("unicode_without_braces", r"\unicode x41"),
+            ("unicode_not_a_number", r"\unicode{abc}"),
+            ("char_surrogate", r#"\char"D800"#),
+            ("caret_notation_surrogate", r"^^^^dfff"),
             ("no_rbrack_instead_of_bracket", r"\sqrt[3\rbrack{1}"),
+            (
+                "invalid_column_spec",
+                r"\begin{array}{lx} a & b \end{array}",
+            ),
+            (
+                "column_spec_with_group",
+                r"\begin{array}{c@{}c} a & b \end{array}",
+            ),
+            (
+                "column_spec_with_width",
+                r"\begin{array}{p{2cm}} a \end{array}",
+            ),
+            (
+                "multicolumn_without_count",
+                r"\begin{matrix} \multicolumn{}{c}{x} \end{matrix}",
+            ),
+            ("multicolumn_outside_table", r"\multicolumn{2}{c}{x}"),
+            ("cline_outside_table", r"a \cline{1-2} b"),
+            (
+                "hdotsfor_invalid_count",
+                r"\begin{matrix} \hdotsfor{x} \end{matrix}",
+            ),
+            (
+                "reversed_cline",
+                r"\begin{matrix} \cline{3-1} a \end{matrix}",
+            ),
+            ("unclosed_column_spec", r"\begin{array}{cc"),
+            (
+                "missing_premises",
+                r"\begin{prooftree} \AxiomC{A} \BinaryInfC{B} \end{prooftree}",
+            ),
+            (
+                "unfinished_proof_tree",
+                r"\begin{prooftree} \AxiomC{A} \AxiomC{B} \end{prooftree}",
+            ),
+            ("axiom_outside_proof_tree", r"\AxiomC{A}"),
+            ("math_in_proof_tree", r"\begin{prooftree} x \end{prooftree}"),
+            ("text_only_command_in_math", r"x = \AA"),
+            ("textyen_in_math", r"\textyen 100"),
+            ("text_only_accent_in_math", r"\c{c}"),
+            ("math_only_command_in_text", r"\text{\alpha}"),
         ];
 
         for (name, problem) in problems.into_iter() {
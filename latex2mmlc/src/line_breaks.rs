@@ -0,0 +1,75 @@
+use mathml_renderer::ast::Node;
+use mathml_renderer::attribute::OpAttr;
+use mathml_renderer::ops;
+
+use crate::commands::get_unicode_symbol;
+use crate::predefined;
+use crate::token::Token;
+
+/// Split the top-level nodes of an inline formula where a line may break.
+///
+/// The formula is split where TeX would allow a line break: after relations and binary
+/// operators. The operator then stays at the end of the chunk before the break, like in
+/// TeX. Explicit breaks (`\\`, `\newline` and `\allowbreak`) split the formula too, and
+/// are dropped, because MathML Core has no way to mark a break inside of `<math>`.
+pub(crate) fn split_at_break_points<'a, 'arena>(
+    nodes: &'a [&'arena Node<'arena>],
+) -> Vec<&'a [&'arena Node<'arena>]> {
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    for (i, node) in nodes.iter().enumerate() {
+        if is_explicit_break(node) {
+            if start < i {
+                chunks.push(&nodes[start..i]);
+            }
+            start = i + 1;
+            continue;
+        }
+        let Some(next) = nodes.get(i + 1) else {
+            break;
+        };
+        // In a run of operators like `= -`, only break after the last one.
+        if is_break_point(node) && !is_break_point(next) {
+            chunks.push(&nodes[start..=i]);
+            start = i + 1;
+        }
+    }
+    if start < nodes.len() || chunks.is_empty() {
+        chunks.push(&nodes[start..]);
+    }
+    chunks
+}
+
+fn is_explicit_break(node: &Node) -> bool {
+    match node {
+        Node::RowSeparator => true,
+        Node::CustomCmd { predefined, .. } => *predefined == &predefined::ALLOWBREAK,
+        _ => false,
+    }
+}
+
+fn is_break_point(node: &Node) -> bool {
+    match node {
+        Node::OpLessThan | Node::OpGreaterThan => true,
+        // A prefix operator like the sign in `= -1` belongs to what follows.
+        Node::Operator(_, Some(OpAttr::FormPrefix)) => false,
+        Node::Operator(op, _) => match op.as_char() {
+            '+' | '−' | '=' => true,
+            ch => match get_unicode_symbol(ch) {
+                Token::BinaryOp(_) => true,
+                Token::Relation(rel) => !matches!(
+                    rel,
+                    ops::FOR_ALL
+                        | ops::THERE_EXISTS
+                        | ops::THERE_DOES_NOT_EXIST
+                        | ops::NOT_SIGN
+                        | ops::HORIZONTAL_ELLIPSIS
+                        | ops::VERTICAL_ELLIPSIS
+                        | ops::DOWN_RIGHT_DIAGONAL_ELLIPSIS
+                ),
+                _ => false,
+            },
+        },
+        _ => false,
+    }
+}
@@ -2,18 +2,21 @@ use std::mem;
 
 use mathml_renderer::{
     arena::{Arena, Buffer, StringBuilder},
-    ast::Node,
+    ast::{table_rows, Node, RuleSpan},
     attribute::{
-        Align, FracAttr, MathSpacing, MathVariant, OpAttr, StretchMode, Style, TextTransform,
+        Align, ColumnAlign, ColumnSpec, FontChange, FracAttr, MathSpacing, MathVariant, OpAttr,
+        StretchMode, Style, TextTransform,
     },
     ops,
 };
 
 use crate::{
-    commands::get_negated_op,
+    commands::{compose_accent, get_negated_op, THIN_SPACE_WIDTH},
     error::{LatexErrKind, LatexError, Place},
     lexer::Lexer,
-    token::{TokLoc, Token},
+    predefined,
+    token::{Braket, Infix, Limits, TokLoc, Token},
+    MathStyle, NumberFormat,
 };
 
 pub(crate) struct Parser<'arena, 'source> {
@@ -22,9 +25,23 @@ pub(crate) struct Parser<'arena, 'source> {
     buffer: Buffer,
     arena: &'arena Arena,
     collector: LetterCollector<'arena>,
-    is_bold_italic: bool,
+    /// Whether the current transform treats upright letters differently,
+    /// like the bold italic of `\boldsymbol`.
+    splits_upright: bool,
     is_after_colon: bool,
     is_after_relation: bool,
+    /// Whether a `d` at this point is a differential: after `\,` or at the start
+    /// of the numerator or the denominator of a fraction.
+    is_differential_position: bool,
+    /// Whether the parser is inside an environment like `array` or `matrix`.
+    in_table: bool,
+    /// The font of the enclosing text commands.
+    text_transform: Option<TextTransform>,
+    math_style: MathStyle,
+    upright_constants: bool,
+    /// Additional command names which work like `\argmax`.
+    limit_operators: &'arena [String],
+    number_format: NumberFormat,
 }
 impl<'arena, 'source> Parser<'arena, 'source>
 where
@@ -38,9 +55,16 @@ where
             buffer: Buffer::new(input_length),
             arena,
             collector: LetterCollector::Inactive,
-            is_bold_italic: false,
+            splits_upright: false,
             is_after_colon: false,
             is_after_relation: false,
+            is_differential_position: false,
+            in_table: false,
+            text_transform: None,
+            math_style: MathStyle::TeX,
+            upright_constants: false,
+            limit_operators: &[],
+            number_format: NumberFormat::default(),
         };
         // Discard the EOF token we just stored in `peek_token`.
         // This loads the first real token into `peek_token`.
@@ -48,6 +72,125 @@ where
         p
     }
 
+    /// Set the letter shapes of the given math style preset.
+    pub(crate) fn with_letter_shapes(mut self, style: MathStyle, upright_constants: bool) -> Self {
+        self.math_style = style;
+        self.upright_constants = upright_constants;
+        self
+    }
+
+    /// Accept the given command names as operators with movable limits, like `\argmax`.
+    pub(crate) fn with_limit_operators(mut self, names: &'arena [String]) -> Self {
+        self.limit_operators = names;
+        self
+    }
+
+    /// Set the separators which combine digits into numbers.
+    pub(crate) fn with_number_format(mut self, format: NumberFormat) -> Self {
+        self.number_format = format;
+        self
+    }
+
+    /// Group the digits of a number in threes with thin spaces, counting from the
+    /// decimal separator, in the parts with five digits or more.
+    ///
+    /// Numbers with anything but digits and one decimal separator are left as they are.
+    fn group_digits(&mut self, number: &'arena str) -> &'arena str {
+        let format = self.number_format;
+        let is_mark = |c: char| c == ops::THIN_SPACE || Some(c) == format.group_separator;
+        let (int, frac) = number
+            .split_once(format.decimal_separator)
+            .unwrap_or((number, ""));
+        if !int
+            .chars()
+            .chain(frac.chars())
+            .all(|c| c.is_ascii_digit() || is_mark(c))
+        {
+            return number;
+        }
+        let digits = |part: &'arena str| part.chars().filter(|c| c.is_ascii_digit());
+        let (n_int, n_frac) = (digits(int).count(), digits(frac).count());
+        let mut builder = self.buffer.get_builder();
+        for (i, digit) in digits(int).enumerate() {
+            if i > 0 && n_int >= 5 && (n_int - i) % 3 == 0 {
+                builder.push_char(ops::THIN_SPACE);
+            }
+            builder.push_char(digit);
+        }
+        if int.len() < number.len() {
+            builder.push_char(format.decimal_separator);
+        }
+        for (i, digit) in digits(frac).enumerate() {
+            if i > 0 && n_frac >= 5 && i % 3 == 0 {
+                builder.push_char(ops::THIN_SPACE);
+            }
+            builder.push_char(digit);
+        }
+        builder.finish(self.arena)
+    }
+
+    /// Overlay the first character of a text with U+0338 COMBINING LONG SOLIDUS OVERLAY,
+    /// as `\not` does with the following character.
+    fn slash_first_char(&mut self, text: &str) -> &'arena str {
+        let mut chars = text.chars();
+        let mut builder = self.buffer.get_builder();
+        if let Some(first) = chars.next() {
+            builder.push_char(first);
+        }
+        builder.push_char('\u{338}');
+        builder.push_str(chars.as_str());
+        builder.finish(self.arena)
+    }
+
+    /// Whether a letter is upright in the current math style.
+    ///
+    /// `upright_token` says whether the letter came in as `Token::UprightLetter`, and
+    /// `is_differential` whether a `d` would be a differential at this position.
+    fn is_upright(&self, c: char, upright_token: bool, is_differential: bool) -> bool {
+        if self.upright_constants && !upright_token {
+            match c {
+                'd' if is_differential => return true,
+                'e' | 'i' | 'π' => return true,
+                _ => {}
+            }
+        }
+        let class = match c {
+            'a'..='z' | 'ı' | 'ȷ' => LetterClass::LatinSmall,
+            'A'..='Z' => LetterClass::LatinCapital,
+            'α'..='ω' | 'ϵ' | 'ϑ' | 'ϰ' | 'ϕ' | 'ϱ' | 'ϖ' | 'ϝ' => LetterClass::GreekSmall,
+            // Greek capitals which don't come as `Token::UprightLetter` were
+            // explicitly italicized, as with `\varGamma`.
+            'Α'..='Ω' | 'ϴ' | 'Ϝ' if upright_token => LetterClass::GreekCapital,
+            _ => return upright_token,
+        };
+        match self.math_style {
+            MathStyle::TeX => matches!(class, LetterClass::GreekCapital),
+            MathStyle::Iso => false,
+            MathStyle::French => !matches!(class, LetterClass::LatinSmall),
+            MathStyle::Upright => true,
+        }
+    }
+
+    /// Resolve the `bold-style` dependent transforms of `\symbf` and `\symbfsf`.
+    fn apply_bold_style(&self, tf: MathVariant) -> MathVariant {
+        let MathVariant::Transform(transform) = tf else {
+            return tf;
+        };
+        MathVariant::Transform(match (transform, self.math_style) {
+            (TextTransform::BoldUprightLatin, MathStyle::Iso) => TextTransform::ExplicitBoldItalic,
+            (TextTransform::BoldUprightLatin, MathStyle::French | MathStyle::Upright) => {
+                TextTransform::Bold
+            }
+            (TextTransform::SansSerifBoldUprightLatin, MathStyle::Iso) => {
+                TextTransform::SansSerifBoldItalic
+            }
+            (TextTransform::SansSerifBoldUprightLatin, MathStyle::French | MathStyle::Upright) => {
+                TextTransform::BoldSansSerif
+            }
+            _ => transform,
+        })
+    }
+
     fn next_token(&mut self) -> TokLoc<'source> {
         if matches!(self.collector, LetterCollector::Collecting) {
             let first_loc = self.peek.location();
@@ -57,11 +200,11 @@ where
 
             // Loop until we find a non-letter token.
             while let tok @ (Token::Letter(ch) | Token::UprightLetter(ch)) = self.peek.token() {
-                // We stop collecting if we encounter an upright letter while in bold italic mode.
-                // This is because the bold-italic transformation has to handle upright letters
+                // We stop collecting if we encounter an upright letter while `splits_upright` is set.
+                // This is because the bold transformations have to handle upright letters
                 // differently, and this wouldn't be possible anymore if we merged these letters
                 // here together with the non-upright letters.
-                if matches!(tok, Token::UprightLetter(_)) && self.is_bold_italic {
+                if matches!(tok, Token::UprightLetter(_)) && self.splits_upright {
                     break;
                 }
                 builder.push_char(*ch);
@@ -99,6 +242,11 @@ where
         Ok(self.arena.push_slice(&nodes))
     }
 
+    /// The commands which were accepted in the wrong mode, see [`Lexer::warnings`].
+    pub(crate) fn into_warnings(self) -> Vec<LatexError<'source>> {
+        self.l.warnings
+    }
+
     /// Parse a sequence of tokens until the given end token is encountered.
     ///
     /// If `eof_as_end_token` is `false`, an error is returned if the input ends before the end
@@ -112,9 +260,42 @@ where
         eof_as_end_token: bool,
     ) -> Result<Vec<&'arena Node<'arena>>, LatexError<'source>> {
         let mut nodes = Vec::new();
+        let mut infix: Option<OpenInfix<'arena>> = None;
 
         // Because we don't want to consume the end token, we just peek here.
         while !self.peek.token().is_same_kind_as(&end_token) {
+            if let &Token::Infix(kind) = self.peek.token() {
+                // `{a \over b \over c}` is ambiguous in TeX; we read it as `{{a \over b} \over c}`.
+                if let Some(open) = infix.take() {
+                    self.finish_infix(&mut nodes, open);
+                }
+                let lt = if matches!(kind, Infix::Above) {
+                    // The length has to be read before the lexer moves past it.
+                    let length = self.l.read_length();
+                    Some(self.parse_line_thickness(length)?)
+                } else {
+                    None
+                };
+                self.next_token(); // Discard the infix token.
+
+                // The numerator starts after the last column or row separator.
+                let num_start = nodes
+                    .iter()
+                    .rposition(|node| matches!(node, Node::ColumnSeparator | Node::RowSeparator))
+                    .map_or(0, |pos| pos + 1);
+                infix = Some(OpenInfix {
+                    kind,
+                    lt,
+                    num_start,
+                    den_start: nodes.len(),
+                });
+                continue;
+            }
+            if matches!(self.peek.token(), Token::Ampersand | Token::NewLine) {
+                if let Some(open) = infix.take() {
+                    self.finish_infix(&mut nodes, open);
+                }
+            }
             let cur_tokloc = self.next_token();
             if matches!(cur_tokloc.token(), Token::EOF) {
                 if eof_as_end_token {
@@ -146,9 +327,72 @@ where
             });
             nodes.push(node);
         }
+        if let Some(open) = infix {
+            self.finish_infix(&mut nodes, open);
+        }
         Ok(nodes)
     }
 
+    /// Replace the numerator and denominator of an infix command like `\over`
+    /// with the fraction they form.
+    fn finish_infix(&mut self, nodes: &mut Vec<&'arena Node<'arena>>, open: OpenInfix<'arena>) {
+        let den = self.node_vec_to_node(nodes.split_off(open.den_start), None);
+        let num = self.node_vec_to_node(nodes.split_off(open.num_start), None);
+        let (lt, fences) = match open.kind {
+            Infix::Over => (None, None),
+            Infix::Atop => (Some(&"0pt"), None),
+            Infix::Above => (open.lt, None),
+            Infix::Choose => (
+                Some(&"0pt"),
+                Some((ops::LEFT_PARENTHESIS, ops::RIGHT_PARENTHESIS)),
+            ),
+            Infix::Brace => (
+                Some(&"0pt"),
+                Some((ops::LEFT_CURLY_BRACKET, ops::RIGHT_CURLY_BRACKET)),
+            ),
+            Infix::Brack => (
+                Some(&"0pt"),
+                Some((ops::LEFT_SQUARE_BRACKET, ops::RIGHT_SQUARE_BRACKET)),
+            ),
+        };
+        let attr = None;
+        let frac = self.commit(Node::Frac { num, den, lt, attr });
+        nodes.push(match fences {
+            Some((open, close)) => self.commit(Node::Fenced {
+                open,
+                close,
+                content: frac,
+                style: None,
+            }),
+            None => frac,
+        });
+    }
+
+    /// Get the line thickness for `\above` and `\genfrac`.
+    ///
+    /// The length must be a decimal number followed by a unit that MathML also understands.
+    fn parse_line_thickness(
+        &self,
+        length: &'source str,
+    ) -> Result<&'arena &'arena str, LatexError<'source>> {
+        let number = ["pt", "pc", "px", "em", "ex", "mm", "cm", "in"]
+            .iter()
+            .find_map(|unit| length.strip_suffix(unit));
+        match number {
+            Some(number)
+                if number.bytes().all(|b| b.is_ascii_digit() || b == b'.')
+                    && number.parse::<f32>().is_ok() =>
+            {
+                // Store the `&str` in the arena so that the node only holds a thin reference.
+                Ok(&self.arena.push_items(&[length])[0])
+            }
+            _ => Err(LatexError(
+                self.peek.location(),
+                LatexErrKind::UnsupportedLength(length),
+            )),
+        }
+    }
+
     /// Put the node onto the heap in the arena and return a reference to it.
     ///
     /// The advantage over using `Box` is that we can store the nodes in a contiguous
@@ -174,41 +418,60 @@ where
         self.is_after_colon = false;
         let is_after_relation = self.is_after_relation;
         self.is_after_relation = false;
+        let is_differential_position = self.is_differential_position;
+        self.is_differential_position = false;
         let node = match cur_token {
             Token::Number(number) => {
                 let mut builder = self.buffer.get_builder();
                 builder.push_char(number as u8 as char);
                 if !wants_arg {
-                    // Consume tokens as long as they are `Token::Number` or
-                    // `Token::Letter(ops::FULL_STOP)` or `Token::Operator(ops::COMMA)`,
-                    // but only if the token *after that* is a digit.
+                    let format = self.number_format;
+                    // Consume tokens as long as they are `Token::Number` or a separator
+                    // of the number format, but only if the token *after that* is a digit.
                     loop {
-                        let ch = if let Token::Number(number) = self.peek.token() {
-                            *number as u8 as char
-                        } else {
-                            let ch = match self.peek.token() {
-                                Token::Letter(ops::FULL_STOP) => Some('.'),
-                                Token::Relation(ops::COMMA) => Some(','),
-                                _ => None,
-                            };
-                            if let Some(ch) = ch {
-                                if self.l.is_next_digit() {
+                        let ch = match self.peek.token() {
+                            Token::Number(number) => *number as u8 as char,
+                            Token::GroupBegin if format.braced_separators => {
+                                match self.l.read_braced_separator(|ch| format.is_separator(ch)) {
+                                    Some(ch) => ch,
+                                    None => break,
+                                }
+                            }
+                            token => {
+                                let ch = match token {
+                                    Token::Letter(ops::FULL_STOP) => '.',
+                                    Token::Relation(ops::COMMA) => ',',
+                                    Token::Space(THIN_SPACE_WIDTH) if format.thin_space_groups => {
+                                        ops::THIN_SPACE
+                                    }
+                                    _ => break,
+                                };
+                                if (ch == ops::THIN_SPACE || format.is_separator(ch))
+                                    && self.l.is_next_digit()
+                                {
                                     ch
                                 } else {
                                     break;
                                 }
-                            } else {
-                                break;
                             }
                         };
                         builder.push_char(ch);
                         next_token(&mut self.peek, &mut self.l);
                     }
                 }
-                Node::Number(builder.finish(self.arena))
+                let number = builder.finish(self.arena);
+                if self.number_format.group_digits {
+                    Node::Number(self.group_digits(number))
+                } else {
+                    Node::Number(number)
+                }
+            }
+            Token::Letter(x) => {
+                Node::SingleLetterIdent(x, self.is_upright(x, false, is_differential_position))
+            }
+            Token::UprightLetter(x) => {
+                Node::SingleLetterIdent(x, self.is_upright(x, true, is_differential_position))
             }
-            Token::Letter(x) => Node::SingleLetterIdent(x, false),
-            Token::UprightLetter(x) => Node::SingleLetterIdent(x, true),
             Token::Relation(relation) => {
                 self.is_after_relation = true;
                 if is_after_colon && matches!(relation, ops::IDENTICAL_TO) {
@@ -221,6 +484,31 @@ where
                     Node::Operator(relation.into(), None)
                 }
             }
+            Token::Dots => match self.peek.token() {
+                // Like amsmath, center the dots between operators and relations,
+                // and before integrals.
+                Token::Integral(_) => Node::CustomCmd {
+                    predefined: &predefined::DOTSI,
+                    args: &[],
+                },
+                Token::Relation(ops::COMMA | ops::SEMICOLON | ops::EXCLAMATION_MARK) => {
+                    self.is_after_relation = true;
+                    Node::Operator(ops::HORIZONTAL_ELLIPSIS.into(), None)
+                }
+                Token::BinaryOp(_)
+                | Token::Relation(_)
+                | Token::BigOp(_)
+                | Token::OpLessThan
+                | Token::OpGreaterThan
+                | Token::Colon => Node::CustomCmd {
+                    predefined: &predefined::CDOTS,
+                    args: &[],
+                },
+                _ => {
+                    self.is_after_relation = true;
+                    Node::Operator(ops::HORIZONTAL_ELLIPSIS.into(), None)
+                }
+            },
             Token::BinaryOp(binary_op) => Node::Operator(
                 binary_op.into(),
                 if is_after_relation {
@@ -233,24 +521,55 @@ where
             Token::OpLessThan => Node::OpLessThan,
             Token::OpAmpersand => Node::OpAmpersand,
             Token::Function(fun) => Node::MultiLetterIdent(fun),
-            Token::Space(space) => Node::Space(space),
+            Token::Space(space) => {
+                // A `d` after a thin space, as in `f(x)\,dx`, is a differential.
+                self.is_differential_position = space == THIN_SPACE_WIDTH;
+                Node::Space(space)
+            }
             Token::NonBreakingSpace | Token::Whitespace => Node::Text("\u{A0}"),
             Token::Sqrt => {
                 let next = self.next_token();
                 if matches!(next.token(), Token::SquareBracketOpen) {
+                    // `\leftroot` and `\uproot` at the start of the index move it.
+                    let (mut left, mut up) = (0, 0);
+                    while let &Token::RootShift(is_up) = self.peek.token() {
+                        self.next_token(); // Discard the shift token.
+                        self.check_lbrace()?;
+                        let (loc, shift) = self.parse_raw_group()?;
+                        let shift = shift
+                            .trim()
+                            .parse()
+                            .map_err(|_| LatexError(loc, LatexErrKind::UnsupportedLength(shift)))?;
+                        if is_up {
+                            up = shift;
+                        } else {
+                            left = shift;
+                        }
+                    }
                     let degree = self.parse_sequence(Token::SquareBracketClose, false)?;
                     self.next_token(); // Discard the closing token.
                     let content = self.parse_next(true)?;
-                    Node::Root(self.node_vec_to_node(degree, None), content)
+                    let mut degree = self.node_vec_to_node(degree, None);
+                    if left != 0 || up != 0 {
+                        degree = self.commit(Node::Shifted {
+                            content: degree,
+                            left,
+                            up,
+                        });
+                    }
+                    Node::Root(degree, content)
                 } else {
                     Node::Sqrt(self.parse_token(next, true)?)
                 }
             }
             Token::Frac(attr) | Token::Binom(attr) => {
+                let is_frac = matches!(cur_token, Token::Frac(_));
+                self.is_differential_position = is_frac;
                 let num = self.parse_next(true)?;
+                self.is_differential_position = is_frac;
                 let den = self.parse_next(true)?;
                 if matches!(cur_token, Token::Binom(_)) {
-                    let lt = Some('0');
+                    let lt = Some(&"0pt");
                     Node::Fenced {
                         open: ops::LEFT_PARENTHESIS,
                         close: ops::RIGHT_PARENTHESIS,
@@ -278,14 +597,10 @@ where
                     _ => return Err(LatexError(0, LatexErrKind::UnexpectedEOF)),
                 };
                 self.check_lbrace()?;
-                // The default line thickness in LaTeX is 0.4pt.
-                // TODO: Support other line thicknesses.
-                // We could maybe store them as multiples of 0.4pt,
-                // so that we can render them as percentages.
-                let lt = match self.parse_text_group()?.trim() {
+                // An empty argument means the default line thickness.
+                let lt = match self.parse_raw_group()?.1.trim() {
                     "" => None,
-                    "0pt" => Some('0'),
-                    _ => return Err(LatexError(0, LatexErrKind::UnexpectedEOF)),
+                    length => Some(self.parse_line_thickness(length)?),
                 };
                 let style = match self.parse_next(true)? {
                     Node::Number(num) => match num.as_bytes() {
@@ -311,9 +626,9 @@ where
             Token::OverUnder(op, is_over, attr) => {
                 let target = self.parse_next(true)?;
                 if is_over {
-                    Node::OverOp(op.into(), attr, target)
+                    Node::OverOp(op, attr, target)
                 } else {
-                    Node::UnderOp(op.into(), target)
+                    Node::UnderOp(op, target)
                 }
             }
             Token::Overset | Token::Underset => {
@@ -327,7 +642,7 @@ where
             }
             Token::OverUnderBrace(x, is_over) => {
                 let target = self.parse_next(true)?;
-                let symbol = self.commit(Node::Operator(x.into(), None));
+                let symbol = self.commit(Node::Operator(x, None));
                 let base = if is_over {
                     Node::Overset { symbol, target }
                 } else {
@@ -355,35 +670,34 @@ where
                 }
             }
             Token::BigOp(op) => {
-                let target = if matches!(self.peek.token(), Token::Limits) {
-                    self.next_token(); // Discard the limits token.
-                    self.commit(Node::Operator(op.into(), Some(OpAttr::NoMovableLimits)))
-                } else {
-                    self.commit(Node::Operator(op.into(), None))
+                let limits = self.parse_limits(Limits::Display);
+                let attr = match limits {
+                    Limits::Always => Some(OpAttr::NoMovableLimits),
+                    Limits::Display | Limits::Never => None,
                 };
-                match self.get_bounds()? {
-                    Bounds(Some(under), Some(over)) => Node::UnderOver {
-                        target,
-                        under,
-                        over,
-                    },
-                    Bounds(Some(symbol), None) => Node::Underset { target, symbol },
-                    Bounds(None, Some(symbol)) => Node::Overset { target, symbol },
-                    Bounds(None, None) => return Ok(target),
-                }
+                let target = self.commit(Node::Operator(op.into(), attr));
+                return self.attach_limits(target, limits);
             }
             Token::Lim(lim) => {
-                if matches!(self.peek.token(), Token::Underscore) {
-                    let target = self.commit(Node::MultiLetterIdent(lim));
-                    self.next_token(); // Discard the underscore token.
-                    let under = self.parse_next(true)?;
-                    Node::Underset {
-                        target,
-                        symbol: under,
-                    }
+                let limits = self.parse_limits(Limits::Display);
+                let bounds = self.get_bounds()?;
+                let attr = named_op_attr(lim, limits, &bounds);
+                let target = self.commit(Node::MultiLetterOperator(lim, attr));
+                return Ok(self.attach_bounds(target, bounds, limits));
+            }
+            Token::VarLim(op, is_over) => {
+                let limits = self.parse_limits(Limits::Display);
+                let bounds = self.get_bounds()?;
+                // The accent keeps `lim` as the core of an embellished operator,
+                // so that the limits behave like those of `\lim`.
+                let attr = named_op_attr("lim", limits, &bounds);
+                let lim = self.commit(Node::MultiLetterOperator("lim", attr));
+                let target = self.commit(if is_over {
+                    Node::OverOp(op, None, lim)
                 } else {
-                    Node::MultiLetterIdent(lim)
-                }
+                    Node::UnderOp(op, lim)
+                });
+                return Ok(self.attach_bounds(target, bounds, limits));
             }
             Token::Slashed => {
                 let node = self.parse_next(true)?;
@@ -393,10 +707,13 @@ where
                 // `\not` has to be followed by something:
                 match self.next_token().into_token() {
                     Token::Relation(op) => {
+                        self.is_after_relation = true;
                         if let Some(negated) = get_negated_op(op) {
                             Node::Operator(negated.into(), None)
                         } else {
-                            Node::Operator(op.into(), None)
+                            // There is no precomposed character, so we overlay
+                            // the relation with a combining long solidus.
+                            Node::Slashed(self.commit(Node::Operator(op.into(), None)))
                         }
                     }
                     Token::OpLessThan => Node::Operator(ops::NOT_LESS_THAN.into(), None),
@@ -407,6 +724,38 @@ where
                         builder.push_char('\u{338}');
                         Node::MultiLetterIdent(builder.finish(self.arena))
                     }
+                    Token::Text(change) => {
+                        let old_collector =
+                            mem::replace(&mut self.collector, LetterCollector::Inactive);
+                        let nodes = self.parse_text_command(loc, change)?;
+                        self.collector = old_collector;
+                        // The first character of the text is overlaid with a solidus;
+                        // without a character, there is nothing to negate.
+                        match nodes.as_slice() {
+                            [Node::Text(text)] if !text.is_empty() => {
+                                Node::Text(self.slash_first_char(text))
+                            }
+                            [Node::TextTransform {
+                                content: Node::Text(text),
+                                tf,
+                            }] if !text.is_empty() => Node::TextTransform {
+                                content: {
+                                    let text = self.slash_first_char(text);
+                                    self.commit(Node::Text(text))
+                                },
+                                tf: *tf,
+                            },
+                            _ => {
+                                return Err(LatexError(
+                                    loc,
+                                    LatexErrKind::CannotBeUsedHere {
+                                        got: cur_token,
+                                        correct_place: Place::BeforeSomeOps,
+                                    },
+                                ))
+                            }
+                        }
+                    }
                     _ => {
                         return Err(LatexError(
                             loc,
@@ -419,39 +768,39 @@ where
                 }
             }
             Token::Transform(tf) => {
-                let old_collector = mem::replace(&mut self.collector, LetterCollector::Collecting);
-                let old_is_bold_italic = mem::replace(
-                    &mut self.is_bold_italic,
-                    matches!(tf, MathVariant::Transform(TextTransform::BoldItalic)),
+                let tf = self.apply_bold_style(tf);
+                // Without a transform, letters stay separate identifiers.
+                let collector = if matches!(tf, MathVariant::Reset) {
+                    LetterCollector::Inactive
+                } else {
+                    LetterCollector::Collecting
+                };
+                let old_collector = mem::replace(&mut self.collector, collector);
+                let old_splits_upright = mem::replace(
+                    &mut self.splits_upright,
+                    matches!(
+                        tf,
+                        MathVariant::Transform(
+                            TextTransform::BoldItalic
+                                | TextTransform::BoldUprightLatin
+                                | TextTransform::SansSerifBoldUprightLatin
+                        )
+                    ),
                 );
                 let content = self.parse_next(true)?;
                 self.collector = old_collector;
-                self.is_bold_italic = old_is_bold_italic;
+                self.splits_upright = old_splits_upright;
                 Node::TextTransform { content, tf }
             }
             Token::Integral(int) => {
-                if matches!(self.peek.token(), Token::Limits) {
-                    self.next_token(); // Discard the limits token.
-                    let target = self.commit(Node::Operator(int.into(), None));
-                    match self.get_bounds()? {
-                        Bounds(Some(under), Some(over)) => Node::UnderOver {
-                            target,
-                            under,
-                            over,
-                        },
-                        Bounds(Some(symbol), None) => Node::Underset { target, symbol },
-                        Bounds(None, Some(symbol)) => Node::Overset { target, symbol },
-                        Bounds(None, None) => return Ok(target),
-                    }
-                } else {
-                    let target = self.commit(Node::Operator(int.into(), None));
-                    match self.get_bounds()? {
-                        Bounds(Some(sub), Some(sup)) => Node::SubSup { target, sub, sup },
-                        Bounds(Some(symbol), None) => Node::Subscript { target, symbol },
-                        Bounds(None, Some(symbol)) => Node::Superscript { target, symbol },
-                        Bounds(None, None) => return Ok(target),
-                    }
-                }
+                let limits = self.parse_limits(Limits::Never);
+                let attr = match limits {
+                    // Integrals don't have movable limits by default.
+                    Limits::Display => Some(OpAttr::MovableLimits),
+                    Limits::Always | Limits::Never => None,
+                };
+                let target = self.commit(Node::Operator(int.into(), attr));
+                return self.attach_limits(target, limits);
             }
             Token::Colon => match &self.peek.token() {
                 Token::Relation(ops::EQUALS_SIGN) if !wants_arg => {
@@ -473,6 +822,8 @@ where
                 },
             },
             Token::GroupBegin => {
+                // The position carries over to the first token of the group.
+                self.is_differential_position = is_differential_position;
                 let content = self.parse_sequence(Token::GroupEnd, false)?;
                 self.next_token(); // Discard the closing token.
                 return Ok(self.node_vec_to_node(content, None));
@@ -526,6 +877,66 @@ where
                     style: None,
                 }
             }
+            Token::Braket(kind) => {
+                let (open, close) = match kind {
+                    Braket::Set | Braket::StretchedSet => {
+                        (ops::LEFT_CURLY_BRACKET, ops::RIGHT_CURLY_BRACKET)
+                    }
+                    Braket::Braket | Braket::StretchedBraket => (
+                        ops::MATHEMATICAL_LEFT_ANGLE_BRACKET,
+                        ops::MATHEMATICAL_RIGHT_ANGLE_BRACKET,
+                    ),
+                };
+                self.check_lbrace()?;
+                self.next_token(); // Discard the opening token.
+                let mut content = self.parse_sequence(Token::GroupEnd, false)?;
+                // Discard the closing token.
+                self.next_token();
+                // Turn the bars on the top level of the argument into dividers:
+                // the first one in a set, and all of them in a braket.
+                let stretched = matches!(kind, Braket::StretchedSet | Braket::StretchedBraket);
+                let mut bars = match kind {
+                    Braket::Set | Braket::StretchedSet => 1,
+                    Braket::Braket | Braket::StretchedBraket => usize::MAX,
+                };
+                for node in content.iter_mut() {
+                    if bars == 0 {
+                        break;
+                    }
+                    let &Node::StretchableOp(op, StretchMode::NoStretch) = *node else {
+                        continue;
+                    };
+                    if op != ops::VERTICAL_LINE && op != ops::DOUBLE_VERTICAL_LINE {
+                        continue;
+                    }
+                    *node = if stretched {
+                        self.commit(Node::StretchableOp(op, StretchMode::Middle))
+                    } else if op == ops::VERTICAL_LINE {
+                        self.commit(Node::Operator(ops::DIVIDES.into(), None))
+                    } else {
+                        self.commit(Node::Operator(ops::PARALLEL_TO.into(), None))
+                    };
+                    bars -= 1;
+                }
+                if stretched {
+                    Node::Fenced {
+                        open,
+                        close,
+                        content: self.node_vec_to_node(content, None),
+                        style: None,
+                    }
+                } else {
+                    content.insert(
+                        0,
+                        self.commit(Node::StretchableOp(open, StretchMode::NoStretch)),
+                    );
+                    content.push(self.commit(Node::StretchableOp(close, StretchMode::NoStretch)));
+                    Node::Row {
+                        nodes: self.arena.push_slice(&content),
+                        style: None,
+                    }
+                }
+            }
             Token::Middle => {
                 let TokLoc(loc, next_token) = self.next_token();
                 let op = match next_token {
@@ -566,62 +977,76 @@ where
                 self.check_lbrace()?;
                 // Read the environment name.
                 let env_name = self.parse_text_group()?;
-                let content = self.parse_sequence(Token::End, false)?;
-                let content = self.arena.push_slice(&content);
-                let end_token_loc = self.next_token().location();
-                let node = match env_name {
-                    "align" | "align*" | "aligned" => Node::Table {
-                        content,
-                        align: Align::Alternating,
-                        attr: Some(FracAttr::DisplayStyleTrue),
-                    },
-                    "cases" => {
-                        let align = Align::Left;
-                        let content = self.commit(Node::Table {
-                            content,
-                            align,
+                let node = if env_name == "prooftree" {
+                    self.parse_proof_tree(loc)?
+                } else {
+                    let columns = if env_name == "array" {
+                        self.check_lbrace()?;
+                        let (loc, spec) = self.parse_raw_group()?;
+                        parse_column_spec(spec)
+                            .ok_or(LatexError(loc, LatexErrKind::InvalidColumnSpec(spec)))?
+                    } else {
+                        Vec::new()
+                    };
+                    let old_in_table = mem::replace(&mut self.in_table, true);
+                    let content = self.parse_sequence(Token::End, false)?;
+                    self.in_table = old_in_table;
+                    let content = self.arena.push_slice(&content);
+                    let rows = table_rows(self.arena, content, &columns);
+                    self.commit(match env_name {
+                        "align" | "align*" | "aligned" => Node::Table {
+                            rows,
+                            align: Align::Alternating,
+                            attr: Some(FracAttr::DisplayStyleTrue),
+                        },
+                        "cases" => {
+                            let align = Align::Left;
+                            let content = self.commit(Node::Table {
+                                rows,
+                                align,
+                                attr: None,
+                            });
+                            Node::Fenced {
+                                open: ops::LEFT_CURLY_BRACKET,
+                                close: ops::NULL,
+                                content,
+                                style: None,
+                            }
+                        }
+                        "matrix" | "array" => Node::Table {
+                            rows,
+                            align: Align::Center,
                             attr: None,
-                        });
-                        Node::Fenced {
-                            open: ops::LEFT_CURLY_BRACKET,
-                            close: ops::NULL,
-                            content,
-                            style: None,
+                        },
+                        matrix_variant @ ("pmatrix" | "bmatrix" | "Bmatrix" | "vmatrix"
+                        | "Vmatrix") => {
+                            let align = Align::Center;
+                            let (open, close) = match matrix_variant {
+                                "pmatrix" => (ops::LEFT_PARENTHESIS, ops::RIGHT_PARENTHESIS),
+                                "bmatrix" => (ops::LEFT_SQUARE_BRACKET, ops::RIGHT_SQUARE_BRACKET),
+                                "Bmatrix" => (ops::LEFT_CURLY_BRACKET, ops::RIGHT_CURLY_BRACKET),
+                                "vmatrix" => (ops::VERTICAL_LINE, ops::VERTICAL_LINE),
+                                "Vmatrix" => (ops::DOUBLE_VERTICAL_LINE, ops::DOUBLE_VERTICAL_LINE),
+                                // SAFETY: `matrix_variant` is one of the strings above.
+                                _ => unsafe { std::hint::unreachable_unchecked() },
+                            };
+                            let attr = None;
+                            Node::Fenced {
+                                open,
+                                close,
+                                content: self.commit(Node::Table { rows, align, attr }),
+                                style: None,
+                            }
                         }
-                    }
-                    "matrix" => Node::Table {
-                        content,
-                        align: Align::Center,
-                        attr: None,
-                    },
-                    matrix_variant
-                    @ ("pmatrix" | "bmatrix" | "Bmatrix" | "vmatrix" | "Vmatrix") => {
-                        let align = Align::Center;
-                        let (open, close) = match matrix_variant {
-                            "pmatrix" => (ops::LEFT_PARENTHESIS, ops::RIGHT_PARENTHESIS),
-                            "bmatrix" => (ops::LEFT_SQUARE_BRACKET, ops::RIGHT_SQUARE_BRACKET),
-                            "Bmatrix" => (ops::LEFT_CURLY_BRACKET, ops::RIGHT_CURLY_BRACKET),
-                            "vmatrix" => (ops::VERTICAL_LINE, ops::VERTICAL_LINE),
-                            "Vmatrix" => (ops::DOUBLE_VERTICAL_LINE, ops::DOUBLE_VERTICAL_LINE),
-                            // SAFETY: `matrix_variant` is one of the strings above.
-                            _ => unsafe { std::hint::unreachable_unchecked() },
-                        };
-                        let attr = None;
-                        Node::Fenced {
-                            open,
-                            close,
-                            content: self.commit(Node::Table {
-                                content,
-                                align,
-                                attr,
-                            }),
-                            style: None,
+                        _ => {
+                            return Err(LatexError(
+                                loc,
+                                LatexErrKind::UnknownEnvironment(env_name),
+                            ));
                         }
-                    }
-                    _ => {
-                        return Err(LatexError(loc, LatexErrKind::UnknownEnvironment(env_name)));
-                    }
+                    })
                 };
+                let end_token_loc = self.next_token().location();
                 self.check_lbrace()?;
                 let end_name = self.parse_text_group()?;
                 if end_name != env_name {
@@ -634,9 +1059,15 @@ where
                     ));
                 }
 
-                node
+                return Ok(node);
             }
             Token::OperatorName => {
+                // `\operatorname*` puts the limits above and below, like `\lim`.
+                let is_starred =
+                    matches!(self.peek.token(), Token::Relation(ops::ASTERISK_OPERATOR));
+                if is_starred {
+                    self.next_token(); // Discard the star.
+                }
                 // TODO: Don't parse a node just to immediately destructure it.
 
                 // Turn off collection mode.
@@ -651,36 +1082,154 @@ where
                     ));
                 }
                 let letters = builder.finish(self.arena);
-                if let Some(ch) = get_single_char(letters) {
+                let limits = self.parse_limits(if is_starred {
+                    Limits::Display
+                } else {
+                    Limits::Never
+                });
+                let bounds = self.get_bounds()?;
+                let target = self.commit(if is_starred || matches!(limits, Limits::Display) {
+                    Node::MultiLetterOperator(letters, named_op_attr(letters, limits, &bounds))
+                } else if let Some(ch) = get_single_char(letters) {
                     Node::SingleLetterIdent(ch, true)
                 } else {
                     Node::MultiLetterIdent(letters)
+                });
+                return Ok(self.attach_bounds(target, bounds, limits));
+            }
+            Token::Text(change) => {
+                // Turn off collection mode.
+                let old_collector = mem::replace(&mut self.collector, LetterCollector::Inactive);
+                let nodes = self.parse_text_command(loc, change)?;
+                self.collector = old_collector;
+                if let [single] = nodes.as_slice() {
+                    return Ok(single);
+                }
+                Node::Row {
+                    nodes: self.arena.push_slice(&nodes),
+                    style: None,
                 }
             }
-            Token::Text(transform) => {
-                self.l.text_mode = true;
+            Token::TextAccent(accent) => {
                 let node = self.parse_next(true)?;
                 let mut builder = self.buffer.get_builder();
                 if !extract_letters(&mut builder, node) {
                     return Err(LatexError(loc, LatexErrKind::ExpectedText("\\text")));
                 }
-                let text = builder.finish(self.arena);
-                self.l.text_mode = false;
-                // Discard any whitespace tokens that are still stored in self.peek_token.
-                if matches!(self.peek.token(), Token::Whitespace) {
-                    self.next_token();
-                }
-                if let Some(transform) = transform {
-                    Node::TextTransform {
-                        content: self.commit(Node::Text(text)),
-                        tf: MathVariant::Transform(transform),
+                let letters = builder.finish(self.arena);
+                let mut chars = letters.chars();
+                let mut builder = self.buffer.get_builder();
+                if let Some(base) = chars.next() {
+                    if let Some(composed) = compose_accent(base, accent) {
+                        builder.push_char(composed);
+                    } else {
+                        builder.push_char(base);
+                        builder.push_char(accent);
                     }
                 } else {
-                    Node::Text(text)
+                    builder.push_char(accent);
                 }
+                builder.push_str(chars.as_str());
+                Node::Text(builder.finish(self.arena))
+            }
+            // `$` is only special inside of text; in math, it's just a letter.
+            Token::MathShift => Node::SingleLetterIdent('$', false),
+            Token::Infix(_) => {
+                return Err(LatexError(
+                    loc,
+                    LatexErrKind::CannotBeUsedHere {
+                        got: cur_token,
+                        correct_place: Place::InsideGroup,
+                    },
+                ));
+            }
+            Token::InlineMathBegin | Token::InlineMathEnd => {
+                return Err(LatexError(
+                    loc,
+                    LatexErrKind::CannotBeUsedHere {
+                        got: cur_token,
+                        correct_place: Place::InText,
+                    },
+                ));
+            }
+            Token::RootShift(_) => {
+                return Err(LatexError(
+                    loc,
+                    LatexErrKind::CannotBeUsedHere {
+                        got: cur_token,
+                        correct_place: Place::StartOfRootIndex,
+                    },
+                ));
+            }
+            Token::Axiom | Token::Inference(_) | Token::RuleLabel(_) => {
+                return Err(LatexError(
+                    loc,
+                    LatexErrKind::CannotBeUsedHere {
+                        got: cur_token,
+                        correct_place: Place::InProofTree,
+                    },
+                ));
             }
             Token::Ampersand => Node::ColumnSeparator,
             Token::NewLine => Node::RowSeparator,
+            Token::MultiColumn | Token::HDotsFor | Token::HLine | Token::CLine
+                if !self.in_table =>
+            {
+                return Err(LatexError(
+                    loc,
+                    LatexErrKind::CannotBeUsedHere {
+                        got: cur_token,
+                        correct_place: Place::InTable,
+                    },
+                ));
+            }
+            Token::MultiColumn => {
+                self.check_lbrace()?;
+                let (loc, columns) = self.parse_raw_group()?;
+                let columns = parse_column_count(columns)
+                    .ok_or(LatexError(loc, LatexErrKind::InvalidColumnCount(columns)))?;
+                self.check_lbrace()?;
+                let (loc, spec) = self.parse_raw_group()?;
+                let spec = match parse_column_spec(spec).as_deref() {
+                    Some(&[column]) => column,
+                    _ => return Err(LatexError(loc, LatexErrKind::InvalidColumnSpec(spec))),
+                };
+                Node::MultiColumn {
+                    columns,
+                    spec: Some(spec),
+                    content: self.parse_next(true)?,
+                }
+            }
+            Token::HDotsFor => {
+                if matches!(self.peek.token(), Token::SquareBracketOpen) {
+                    // The spacing of the dots is ignored.
+                    self.next_token();
+                    self.parse_sequence(Token::SquareBracketClose, false)?;
+                    self.next_token(); // Discard the closing token.
+                }
+                self.check_lbrace()?;
+                let (loc, columns) = self.parse_raw_group()?;
+                let columns = parse_column_count(columns)
+                    .ok_or(LatexError(loc, LatexErrKind::InvalidColumnCount(columns)))?;
+                Node::MultiColumn {
+                    columns,
+                    spec: None,
+                    content: self.commit(Node::Operator(ops::HORIZONTAL_ELLIPSIS.into(), None)),
+                }
+            }
+            Token::HLine => Node::HorizontalRule(RuleSpan::Full),
+            Token::CLine => {
+                self.check_lbrace()?;
+                let (loc, range) = self.parse_raw_group()?;
+                let span = range
+                    .split_once('-')
+                    .and_then(|(first, last)| {
+                        Some((parse_column_count(first)?, parse_column_count(last)?))
+                    })
+                    .filter(|(first, last)| first <= last)
+                    .ok_or(LatexError(loc, LatexErrKind::InvalidColumnRange(range)))?;
+                Node::HorizontalRule(RuleSpan::Columns(span.0, span.1))
+            }
             Token::Style(style) => {
                 let content = self.parse_sequence(Token::GroupEnd, true)?;
                 Node::Row {
@@ -688,9 +1237,35 @@ where
                     style: Some(style),
                 }
             }
+            Token::FontSize(size) => {
+                let content = self.parse_sequence(Token::GroupEnd, true)?;
+                Node::SizedRow {
+                    nodes: self.arena.push_slice(&content),
+                    size,
+                }
+            }
             Token::UnknownCommand(name) => {
+                if self.limit_operators.iter().any(|op| op == name) {
+                    let limits = self.parse_limits(Limits::Display);
+                    let bounds = self.get_bounds()?;
+                    let attr = named_op_attr(name, limits, &bounds);
+                    let target = self.commit(Node::MultiLetterOperator(name, attr));
+                    return Ok(self.attach_bounds(target, bounds, limits));
+                }
                 return Err(LatexError(loc, LatexErrKind::UnknownCommand(name)));
             }
+            Token::TextOnlyCommand(name) => {
+                return Err(LatexError(loc, LatexErrKind::TextOnlyCommand(name)));
+            }
+            Token::MathOnlyCommand(name) => {
+                return Err(LatexError(loc, LatexErrKind::MathOnlyCommand(name)));
+            }
+            Token::InvalidCodePoint(code) => {
+                return Err(LatexError(loc, LatexErrKind::InvalidCodePoint(code)));
+            }
+            Token::SurrogateCodePoint(code) => {
+                return Err(LatexError(loc, LatexErrKind::SurrogateCodePoint(code)));
+            }
             // Token::Underscore | Token::Circumflex => {
             Token::Circumflex => {
                 return Err(LatexError(
@@ -701,12 +1276,13 @@ where
                     },
                 ));
             }
-            Token::Prime => {
+            Token::Prime(count) => {
                 let target = self.commit(Node::Row {
                     nodes: &[],
                     style: None,
                 });
-                let symbol = self.commit(Node::Operator(ops::PRIME.into(), None));
+                let op = PRIME_SELECTION[usize::from(count) - 1];
+                let symbol = self.commit(Node::Operator(op.into(), None));
                 Node::Superscript { target, symbol }
             }
             Token::Underscore => {
@@ -714,7 +1290,7 @@ where
                 let base = self.parse_next(false)?;
                 Node::Multiscript { base, sub }
             }
-            Token::Limits => {
+            Token::Limits(_) => {
                 return Err(LatexError(
                     loc,
                     LatexErrKind::CannotBeUsedHere {
@@ -768,6 +1344,292 @@ where
         self.parse_token(token, wants_arg)
     }
 
+    /// Parse the body of a `prooftree` environment from bussproofs.
+    ///
+    /// The commands build the proof on a stack: `\AxiomC` pushes a leaf, and
+    /// `\UnaryInfC`, `\BinaryInfC` and `\TrinaryInfC` replace the top one, two or
+    /// three proofs with an inference from them. Every step is a fraction with the
+    /// premises in the numerator, so that the conclusions of sibling proofs line up;
+    /// the leaves have an invisible rule.
+    fn parse_proof_tree(
+        &mut self,
+        loc: usize,
+    ) -> Result<&'arena Node<'arena>, LatexError<'source>> {
+        let mut stack: Vec<&'arena Node<'arena>> = Vec::new();
+        let mut left_label = None;
+        let mut right_label = None;
+        loop {
+            let TokLoc(token_loc, token) = match self.peek.token() {
+                Token::End => break,
+                Token::EOF => {
+                    return Err(LatexError(
+                        self.peek.location(),
+                        LatexErrKind::UnclosedGroup(Token::End),
+                    ));
+                }
+                _ => self.next_token(),
+            };
+            let premises = match token {
+                Token::Axiom => 0,
+                Token::Inference(premises) => premises,
+                Token::RuleLabel(is_left) => {
+                    let label = self.parse_proof_arg(token_loc)?;
+                    if is_left {
+                        left_label = Some(label);
+                    } else {
+                        right_label = Some(label);
+                    }
+                    continue;
+                }
+                _ => {
+                    return Err(LatexError(
+                        token_loc,
+                        LatexErrKind::UnexpectedToken {
+                            expected: &Token::Axiom,
+                            got: token,
+                        },
+                    ));
+                }
+            };
+            let conclusion = self.parse_proof_arg(token_loc)?;
+            let Some(start) = stack.len().checked_sub(usize::from(premises)) else {
+                return Err(LatexError(
+                    token_loc,
+                    LatexErrKind::MissingPremises(premises),
+                ));
+            };
+            let mut num = Vec::with_capacity(2 * usize::from(premises));
+            for (i, premise) in stack.drain(start..).enumerate() {
+                if i > 0 {
+                    num.push(self.commit(Node::Space("1.5")));
+                }
+                num.push(premise);
+            }
+            let frac = self.commit(Node::Frac {
+                num: self.node_vec_to_node(num, None),
+                den: conclusion,
+                lt: if premises == 0 { Some(&"0pt") } else { None },
+                attr: Some(FracAttr::DisplayStyleTrue),
+            });
+            if premises == 0 {
+                // Labels belong to the next rule with premises.
+                stack.push(frac);
+                continue;
+            }
+            let (left, right) = (left_label.take(), right_label.take());
+            if left.is_none() && right.is_none() {
+                stack.push(frac);
+                continue;
+            }
+            let mut nodes = Vec::with_capacity(5);
+            if let Some(left) = left {
+                nodes.push(left);
+                nodes.push(self.commit(Node::Space("0.3")));
+            }
+            nodes.push(frac);
+            if let Some(right) = right {
+                nodes.push(self.commit(Node::Space("0.3")));
+                nodes.push(right);
+            }
+            stack.push(self.commit(Node::Row {
+                nodes: self.arena.push_slice(&nodes),
+                style: None,
+            }));
+        }
+        match stack.as_slice() {
+            [tree] => Ok(tree),
+            _ => Err(LatexError(
+                loc,
+                LatexErrKind::UnfinishedProofTree(stack.len()),
+            )),
+        }
+    }
+
+    /// Parse the argument of a bussproofs command, which is text with optional math.
+    fn parse_proof_arg(&mut self, loc: usize) -> Result<&'arena Node<'arena>, LatexError<'source>> {
+        // Turn off collection mode.
+        let old_collector = mem::replace(&mut self.collector, LetterCollector::Inactive);
+        let nodes = self.parse_text_command(loc, FontChange::Inherit)?;
+        self.collector = old_collector;
+        if let [single] = nodes.as_slice() {
+            return Ok(single);
+        }
+        Ok(self.commit(Node::Row {
+            nodes: self.arena.push_slice(&nodes),
+            style: None,
+        }))
+    }
+
+    /// Parse a text command like `\textbf` with its argument.
+    ///
+    /// The font change is applied on top of the font of any enclosing text command.
+    fn parse_text_command(
+        &mut self,
+        loc: usize,
+        change: FontChange,
+    ) -> Result<Vec<&'arena Node<'arena>>, LatexError<'source>> {
+        let old_transform = self.text_transform;
+        self.text_transform = change.apply(old_transform);
+        let nodes = self.parse_text_arg(loc);
+        self.text_transform = old_transform;
+        nodes
+    }
+
+    /// Parse the argument of `\text` and its relatives.
+    ///
+    /// Runs of text become `Node::Text`, while math inside `$...$` or `\(...\)`
+    /// is parsed in math mode and becomes a row next to the text.
+    ///
+    /// The lexer is switched between text and math mode right before the token
+    /// which ends the current mode is consumed, so that the token after it
+    /// is already lexed in the right mode.
+    fn parse_text_arg(
+        &mut self,
+        loc: usize,
+    ) -> Result<Vec<&'arena Node<'arena>>, LatexError<'source>> {
+        let mut nodes = Vec::new();
+        let mut run = Vec::new();
+        if !matches!(self.peek.token(), Token::GroupBegin) {
+            // The argument is a single token.
+            run.push(self.parse_next(true)?);
+        } else {
+            // `\text` can also appear inside text.
+            let was_text_mode = mem::replace(&mut self.l.text_mode, true);
+            self.next_token(); // Discard the opening token.
+            let mut depth = 0usize;
+            loop {
+                match self.peek.token() {
+                    Token::GroupEnd if depth == 0 => {
+                        self.l.text_mode = was_text_mode;
+                        self.next_token(); // Discard the closing token.
+                        break;
+                    }
+                    Token::GroupBegin => {
+                        depth += 1;
+                        self.next_token();
+                    }
+                    Token::GroupEnd => {
+                        depth -= 1;
+                        self.next_token();
+                    }
+                    Token::MathShift | Token::InlineMathBegin => {
+                        let end_token = if matches!(self.peek.token(), Token::MathShift) {
+                            Token::MathShift
+                        } else {
+                            Token::InlineMathEnd
+                        };
+                        self.l.text_mode = false;
+                        self.next_token(); // Discard the opening token.
+                        let content = self.parse_sequence(end_token, false)?;
+                        self.l.text_mode = true;
+                        self.next_token(); // Discard the closing token.
+                        self.finish_text_run(loc, &mut run, &mut nodes)?;
+                        nodes.push(self.commit(Node::Row {
+                            nodes: self.arena.push_slice(&content),
+                            style: None,
+                        }));
+                    }
+                    &Token::Text(change) => {
+                        self.finish_text_run(loc, &mut run, &mut nodes)?;
+                        let inner_loc = self.next_token().location();
+                        for piece in self.parse_text_command(inner_loc, change)? {
+                            self.push_text_piece(&mut nodes, piece);
+                        }
+                    }
+                    Token::EOF => {
+                        return Err(LatexError(
+                            self.peek.location(),
+                            LatexErrKind::UnclosedGroup(Token::GroupEnd),
+                        ));
+                    }
+                    _ => {
+                        let tokloc = self.next_token();
+                        run.push(self.parse_token(tokloc, true)?);
+                    }
+                }
+            }
+        }
+        if run.is_empty() && nodes.is_empty() {
+            // An empty argument still produces an (empty) text node.
+            run.push(self.commit(Node::Text("")));
+        }
+        self.finish_text_run(loc, &mut run, &mut nodes)?;
+        Ok(nodes)
+    }
+
+    /// Turn the nodes collected in text mode into a single text node,
+    /// in the current text font.
+    fn finish_text_run(
+        &mut self,
+        loc: usize,
+        run: &mut Vec<&'arena Node<'arena>>,
+        nodes: &mut Vec<&'arena Node<'arena>>,
+    ) -> Result<(), LatexError<'source>> {
+        if run.is_empty() {
+            return Ok(());
+        }
+        let mut builder = self.buffer.get_builder();
+        for node in run.drain(..) {
+            if !extract_letters(&mut builder, node) {
+                return Err(LatexError(loc, LatexErrKind::ExpectedText("\\text")));
+            }
+        }
+        let text = builder.finish(self.arena);
+        let text = self.commit(Node::Text(text));
+        let piece = if let Some(transform) = self.text_transform {
+            self.commit(Node::TextTransform {
+                content: text,
+                tf: MathVariant::Transform(transform),
+            })
+        } else {
+            text
+        };
+        self.push_text_piece(nodes, piece);
+        Ok(())
+    }
+
+    /// Add a piece of text, merging it with the previous one if it has the same font.
+    fn push_text_piece(
+        &mut self,
+        nodes: &mut Vec<&'arena Node<'arena>>,
+        piece: &'arena Node<'arena>,
+    ) {
+        if let Some(last) = nodes.last_mut() {
+            if let Some(merged) = self.merge_text(last, piece) {
+                *last = merged;
+                return;
+            }
+        }
+        nodes.push(piece);
+    }
+
+    fn merge_text(
+        &mut self,
+        first: &'arena Node<'arena>,
+        second: &'arena Node<'arena>,
+    ) -> Option<&'arena Node<'arena>> {
+        match (first, second) {
+            (Node::Text(first), Node::Text(second)) => {
+                let mut builder = self.buffer.get_builder();
+                builder.push_str(first);
+                builder.push_str(second);
+                let text = builder.finish(self.arena);
+                Some(self.commit(Node::Text(text)))
+            }
+            (
+                Node::TextTransform { content, tf },
+                Node::TextTransform {
+                    content: second,
+                    tf: second_tf,
+                },
+            ) if tf == second_tf => {
+                let content = self.merge_text(content, second)?;
+                Some(self.commit(Node::TextTransform { content, tf: *tf }))
+            }
+            _ => None,
+        }
+    }
+
     /// Parse the contents of a group which can only contain text.
     fn parse_text_group(&mut self) -> Result<&'source str, LatexError<'source>> {
         let result = self.l.read_environment_name();
@@ -776,6 +1638,22 @@ where
         result.ok_or(LatexError(opening_loc, LatexErrKind::UnparsableEnvName))
     }
 
+    /// Read the raw text of a group, like the column specification of an `array`.
+    ///
+    /// Returns the location of the text along with the text.
+    fn parse_raw_group(&mut self) -> Result<(usize, &'source str), LatexError<'source>> {
+        let result = self.l.read_raw_group();
+        // Discard the opening token (which is still stored as `peek`).
+        let opening_loc = self.next_token().location();
+        match result {
+            Some(text) => Ok((opening_loc + 1, text)),
+            None => Err(LatexError(
+                opening_loc,
+                LatexErrKind::UnclosedGroup(Token::GroupEnd),
+            )),
+        }
+    }
+
     fn check_lbrace(&mut self) -> Result<(), LatexError<'source>> {
         if !matches!(self.peek.token(), Token::GroupBegin) {
             let TokLoc(loc, token) = self.next_token();
@@ -852,20 +1730,62 @@ where
         Ok(Bounds(sub, sup))
     }
 
+    /// Read `\limits`, `\nolimits` and `\displaylimits` after a large operator.
+    ///
+    /// Like in TeX, the last one wins.
+    fn parse_limits(&mut self, default: Limits) -> Limits {
+        let mut limits = default;
+        while let Token::Limits(next) = *self.peek.token() {
+            self.next_token(); // Discard the limits token.
+            limits = next;
+        }
+        limits
+    }
+
+    /// Attach the bounds of a large operator, either above and below it or at its side.
+    ///
+    /// With `Limits::Display`, the target has to be an operator with movable limits,
+    /// so that the bounds move to the side outside of display style.
+    fn attach_limits(
+        &mut self,
+        target: &'arena Node<'arena>,
+        limits: Limits,
+    ) -> Result<&'arena Node<'arena>, LatexError<'source>> {
+        let bounds = self.get_bounds()?;
+        Ok(self.attach_bounds(target, bounds, limits))
+    }
+
+    /// Attach bounds which have already been parsed, like [`Parser::attach_limits`].
+    fn attach_bounds(
+        &mut self,
+        target: &'arena Node<'arena>,
+        bounds: Bounds<'arena>,
+        limits: Limits,
+    ) -> &'arena Node<'arena> {
+        let node = match (bounds, limits) {
+            (Bounds(None, None), _) => return target,
+            (Bounds(Some(sub), Some(sup)), Limits::Never) => Node::SubSup { target, sub, sup },
+            (Bounds(Some(symbol), None), Limits::Never) => Node::Subscript { target, symbol },
+            (Bounds(None, Some(symbol)), Limits::Never) => Node::Superscript { target, symbol },
+            (Bounds(Some(under), Some(over)), _) => Node::UnderOver {
+                target,
+                under,
+                over,
+            },
+            (Bounds(Some(symbol), None), _) => Node::Underset { target, symbol },
+            (Bounds(None, Some(symbol)), _) => Node::Overset { target, symbol },
+        };
+        self.commit(node)
+    }
+
     /// Check for primes and aggregate them into a single node.
     fn prime_check(&mut self) -> Vec<&'arena Node<'arena>> {
         let mut primes = Vec::new();
         let mut prime_count = 0usize;
-        while matches!(self.peek.token(), Token::Prime) {
+        while let Token::Prime(count) = *self.peek.token() {
             self.next_token(); // Discard the prime token.
-            prime_count += 1;
+            prime_count += count as usize;
         }
-        static PRIME_SELECTION: [ops::Rel; 4] = [
-            ops::PRIME,
-            ops::DOUBLE_PRIME,
-            ops::TRIPLE_PRIME,
-            ops::QUADRUPLE_PRIME,
-        ];
         if prime_count > 0 {
             // If we have between 1 and 4 primes, we can use the predefined prime operators.
             if let Some(op) = PRIME_SELECTION.get(prime_count - 1) {
@@ -888,7 +1808,7 @@ where
         let next = self.next_token();
         if matches!(
             next.token(),
-            Token::Underscore | Token::Circumflex | Token::Prime
+            Token::Underscore | Token::Circumflex | Token::Prime(_)
         ) {
             return Err(LatexError(
                 next.location(),
@@ -901,11 +1821,11 @@ where
         let node = self.parse_token(next, true);
 
         // If the bound was a superscript, it may *not* be followed by a prime.
-        if is_sup && matches!(self.peek.token(), Token::Prime) {
+        if let (true, &Token::Prime(count)) = (is_sup, self.peek.token()) {
             return Err(LatexError(
                 self.peek.location(),
                 LatexErrKind::CannotBeUsedHere {
-                    got: Token::Prime,
+                    got: Token::Prime(count),
                     correct_place: Place::AfterOpOrIdent,
                 },
             ));
@@ -942,6 +1862,23 @@ fn next_token<'source>(peek: &mut TokLoc<'source>, lexer: &mut Lexer<'source>) -
 
 struct Bounds<'arena>(Option<&'arena Node<'arena>>, Option<&'arena Node<'arena>>);
 
+/// An infix command like `\over` whose denominator is still being parsed.
+struct OpenInfix<'arena> {
+    kind: Infix,
+    lt: Option<&'arena &'arena str>,
+    /// Index in the node list where the numerator starts.
+    num_start: usize,
+    /// Index in the node list where the denominator starts.
+    den_start: usize,
+}
+
+enum LetterClass {
+    LatinSmall,
+    LatinCapital,
+    GreekSmall,
+    GreekCapital,
+}
+
 enum LetterCollector<'arena> {
     Inactive,
     Collecting,
@@ -949,6 +1886,13 @@ enum LetterCollector<'arena> {
     FinishedManyLetters { collected_letters: &'arena str },
 }
 
+static PRIME_SELECTION: [ops::Rel; 4] = [
+    ops::PRIME,
+    ops::DOUBLE_PRIME,
+    ops::TRIPLE_PRIME,
+    ops::QUADRUPLE_PRIME,
+];
+
 /// Extract the text of all single-letter identifiers and operators in `node`.
 /// This function cannot be a method, because we need to borrow arena immutably
 /// but buffer mutably. This is not possible with a mutable self reference.
@@ -979,6 +1923,22 @@ fn extract_letters<'arena>(buffer: &mut StringBuilder, node: &'arena Node<'arena
     true
 }
 
+/// The attribute for a named operator like `\lim` or `\limsup`.
+///
+/// Only `lim`, `max` and `min` are in the operator dictionary; the other names need
+/// the spacing of `lim`. `movablelimits` is only given if there are limits to move.
+fn named_op_attr(name: &str, limits: Limits, bounds: &Bounds) -> Option<OpAttr> {
+    let has_bounds = !matches!(bounds, Bounds(None, None));
+    let in_dictionary = matches!(name, "lim" | "max" | "min");
+    match (limits, has_bounds, in_dictionary) {
+        (Limits::Display, true, true) => Some(OpAttr::MovableLimits),
+        (Limits::Always, true, true) => Some(OpAttr::NoMovableLimits),
+        (_, _, true) => None,
+        (Limits::Display, true, false) => Some(OpAttr::NameSpacingMovableLimits),
+        (_, _, false) => Some(OpAttr::NameSpacing),
+    }
+}
+
 fn get_single_char(s: &str) -> Option<char> {
     let mut chars = s.chars();
     match (chars.next(), chars.next()) {
@@ -987,6 +1947,46 @@ fn get_single_char(s: &str) -> Option<char> {
     }
 }
 
+/// Parse the column specification of an `array`, like `l|cr`.
+///
+/// Only the alignments `l`, `c` and `r` and vertical rules are supported.
+fn parse_column_spec(spec: &str) -> Option<Vec<ColumnSpec>> {
+    let mut columns: Vec<ColumnSpec> = Vec::new();
+    let mut line_before = false;
+    for c in spec.chars() {
+        let align = match c {
+            'l' => ColumnAlign::Left,
+            'c' => ColumnAlign::Center,
+            'r' => ColumnAlign::Right,
+            '|' => {
+                // A rule between two columns is drawn by the one on the left.
+                match columns.last_mut() {
+                    Some(column) => column.line_after = true,
+                    None => line_before = true,
+                }
+                continue;
+            }
+            c if c.is_ascii_whitespace() => continue,
+            _ => return None,
+        };
+        columns.push(ColumnSpec {
+            align,
+            line_before: mem::take(&mut line_before),
+            line_after: false,
+        });
+    }
+    if columns.is_empty() {
+        None
+    } else {
+        Some(columns)
+    }
+}
+
+/// Parse a positive number of columns, like the first argument of `\multicolumn`.
+fn parse_column_count(count: &str) -> Option<u16> {
+    count.trim().parse().ok().filter(|&count| count > 0)
+}
+
 #[cfg(test)]
 mod tests {
     use insta::assert_ron_snapshot;
@@ -14,6 +14,28 @@ pub static MOD: Node = Row {
     style: None,
 };
 
+/// Only a marker for splitting inline formulas; it renders as nothing.
+pub static ALLOWBREAK: Node = HardcodedMathML("");
+
+pub static CDOTS: Node = Row {
+    nodes: &[
+        &Operator(ops::MIDDLE_DOT.as_op(), None),
+        &OperatorWithSpacing {
+            op: ops::MIDDLE_DOT.as_op(),
+            left: Some(MathSpacing::Zero),
+            right: Some(MathSpacing::Zero),
+        },
+        &Operator(ops::MIDDLE_DOT.as_op(), None),
+    ],
+    style: None,
+};
+
+/// `\dotsi`: centered dots pulled towards the preceding integral.
+pub static DOTSI: Node = Row {
+    nodes: &[&Space("-0.1667"), &CDOTS],
+    style: None,
+};
+
 pub static PMOD: Node = Row {
     nodes: &[
         &Space("1"),
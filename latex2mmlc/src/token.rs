@@ -1,7 +1,9 @@
 use std::mem::discriminant;
 
 use mathml_renderer::ast::Node;
-use mathml_renderer::attribute::{FracAttr, MathVariant, OpAttr, Size, Style, TextTransform};
+use mathml_renderer::attribute::{
+    FontChange, FontSize, FracAttr, MathVariant, OpAttr, Size, Style,
+};
 use mathml_renderer::ops::{Big, Bin, Op, ParenOp, Rel};
 use strum_macros::AsRefStr;
 
@@ -18,12 +20,31 @@ pub enum Token<'source> {
     Ampersand,
     #[strum(serialize = r"\\")]
     NewLine,
+    #[strum(serialize = r"\multicolumn")]
+    MultiColumn,
+    #[strum(serialize = r"\hdotsfor")]
+    HDotsFor,
+    #[strum(serialize = r"\hline")]
+    HLine,
+    #[strum(serialize = r"\cline")]
+    CLine,
+    /// `\AxiomC` of bussproofs.
+    #[strum(serialize = r"\AxiomC")]
+    Axiom,
+    /// `\UnaryInfC`, `\BinaryInfC` or `\TrinaryInfC` of bussproofs, with the number
+    /// of premises.
+    #[strum(serialize = "inference rule")]
+    Inference(u8),
+    /// `\LeftLabel` (`true`) or `\RightLabel` (`false`) of bussproofs.
+    #[strum(serialize = "rule label")]
+    RuleLabel(bool),
     #[strum(serialize = r"\left")]
     Left,
     #[strum(serialize = r"\right")]
     Right,
     #[strum(serialize = r"\middle")]
     Middle,
+    Braket(Braket),
     #[strum(serialize = "parenthesis")]
     Delimiter(&'static ParenOp),
     /// The opening square bracket has its own token because we need to
@@ -47,6 +68,9 @@ pub enum Token<'source> {
     #[strum(serialize = "^")]
     Circumflex,
     Binom(Option<FracAttr>),
+    /// An infix command like `\over`, which splits the enclosing group
+    /// into numerator and denominator.
+    Infix(Infix),
     #[strum(serialize = r"\overset")]
     Overset,
     #[strum(serialize = r"\underset")]
@@ -54,10 +78,15 @@ pub enum Token<'source> {
     OverUnderBrace(Op, bool),
     #[strum(serialize = r"\sqrt")]
     Sqrt,
+    /// `\uproot` (`true`) or `\leftroot` (`false`).
+    #[strum(serialize = r"\leftroot, \uproot")]
+    RootShift(bool),
     Integral(Big),
     #[strum(serialize = r"\limits")]
-    Limits,
+    Limits(Limits),
     Lim(&'static str),
+    /// `\lim` with an accent above (`true`) or below (`false`), like `\varlimsup`.
+    VarLim(Op, bool),
     Space(&'static str),
     #[strum(serialize = "~")]
     NonBreakingSpace,
@@ -66,10 +95,14 @@ pub enum Token<'source> {
     Big(Size),
     OverUnder(Op, bool, Option<OpAttr>),
     Relation(Rel),
+    /// `\dots`, which picks baseline or centered dots from the next token.
+    #[strum(serialize = r"\dots")]
+    Dots,
     #[strum(serialize = "binary operator")]
     BinaryOp(Bin),
+    /// One or more primes; `′`, `″`, `‴` and `⁗` count as several at once.
     #[strum(serialize = "'")]
-    Prime,
+    Prime(u8),
     #[strum(serialize = ">")]
     OpGreaterThan,
     #[strum(serialize = "<")]
@@ -89,12 +122,30 @@ pub enum Token<'source> {
     #[strum(serialize = r"\not")]
     Not,
     #[strum(serialize = r"\text*")]
-    Text(Option<TextTransform>),
+    Text(FontChange),
+    /// `$` starts and ends math inside of text.
+    #[strum(serialize = "$")]
+    MathShift,
+    #[strum(serialize = r"\(")]
+    InlineMathBegin,
+    #[strum(serialize = r"\)")]
+    InlineMathEnd,
+    /// An accent command like `\'` in text mode, with its combining character.
+    TextAccent(char),
     Style(Style),
+    FontSize(FontSize),
     CustomCmd(usize, &'static Node<'static>),
     GetCollectedLetters,
     HardcodedMathML(&'static str),
     UnknownCommand(&'source str),
+    /// A command like `\AA` which is only valid in text mode, found in math mode.
+    TextOnlyCommand(&'source str),
+    /// A command like `\alpha` which is only valid in math mode, found in text mode.
+    MathOnlyCommand(&'source str),
+    /// A character given by its code point, like `\char"110000`, which is out of range.
+    InvalidCodePoint(&'source str),
+    /// A character given by its code point which is a UTF-16 surrogate.
+    SurrogateCodePoint(&'source str),
 }
 
 impl Token<'_> {
@@ -105,6 +156,40 @@ impl Token<'_> {
     }
 }
 
+/// A command of the braket package.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Braket {
+    /// `\set`: braces, with the first bar as `\mid`.
+    Set,
+    /// `\Set`: stretched braces, with the first bar as `\middle|`.
+    StretchedSet,
+    /// `\braket`: angle brackets.
+    Braket,
+    /// `\Braket`: stretched angle brackets, with every bar as `\middle|`.
+    StretchedBraket,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Infix {
+    Over,
+    Atop,
+    Above,
+    Choose,
+    Brace,
+    Brack,
+}
+
+/// Where the limits of a large operator are placed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Limits {
+    /// `\displaylimits`: above and below in display style, at the side otherwise.
+    Display,
+    /// `\limits`: always above and below.
+    Always,
+    /// `\nolimits`: always at the side.
+    Never,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[repr(u8)]
 pub enum Digit {
@@ -4,7 +4,7 @@ use insta::assert_snapshot;
 use regex::Regex;
 // use similar::{ChangeTag, TextDiff};
 
-use latex2mmlc::{latex_to_mathml, Display};
+use latex2mmlc::{latex_to_mathml, latex_to_mathml_with_config, Config, Display};
 
 #[test]
 fn wiki_test() {
@@ -34,7 +34,7 @@ fn wiki_test() {
     (r"dy/dx, \mathrm{d}y/\mathrm{d}x, \frac{dy}{dx}, \frac{\mathrm{d}y}{\mathrm{d}x}, \frac{\partial^2} {\partial x_1\partial x_2}y", "<math><mrow><mi>d</mi><mi>y</mi><mi>/</mi><mi>d</mi><mi>x</mi><mo>,</mo></mrow><mrow><mrow><mi mathvariant=\"normal\">d</mi></mrow><mi>y</mi><mi>/</mi><mrow><mi mathvariant=\"normal\">d</mi></mrow><mi>x</mi><mo>,</mo></mrow><mrow><mfrac><mrow><mi>d</mi><mi>y</mi></mrow><mrow><mi>d</mi><mi>x</mi></mrow></mfrac><mo>,</mo></mrow><mrow><mfrac><mrow><mrow><mi mathvariant=\"normal\">d</mi></mrow><mi>y</mi></mrow><mrow><mrow><mi mathvariant=\"normal\">d</mi></mrow><mi>x</mi></mrow></mfrac><mo>,</mo></mrow><mrow><mfrac><msup><mi>∂</mi><mn>2</mn></msup><mrow><mi>∂</mi><msub><mi>x</mi><mn>1</mn></msub><mi>∂</mi><msub><mi>x</mi><mn>2</mn></msub></mrow></mfrac><mi>y</mi></mrow></math>"),
     (r"\prime, \backprime, f^\prime, f', f'', f^{(3)}, \dot y, \ddot y", "<math><mrow><mo>′</mo></mrow><mrow><mo>,</mo></mrow><mrow><mo>‵</mo></mrow><mrow><mo>,</mo></mrow><mrow><msup><mi>f</mi><mo class=\"tml-prime prime-pad\">′</mo></msup><mo>,</mo></mrow><mrow><msup><mi>f</mi><mo class=\"tml-prime prime-pad\" lspace=\"0em\" rspace=\"0em\">′</mo></msup><mo>,</mo></mrow><mrow><msup><mi>f</mi><mrow><mo class=\"tml-prime prime-pad\" lspace=\"0em\" rspace=\"0em\">′</mo><mo lspace=\"0em\" rspace=\"0em\">′</mo></mrow></msup><mo>,</mo></mrow><mrow><msup><mi>f</mi><mrow><mo form=\"prefix\" lspace=\"0em\" rspace=\"0em\" stretchy=\"false\">(</mo><mn>3</mn><mo form=\"postfix\" lspace=\"0em\" rspace=\"0em\" stretchy=\"false\">)</mo></mrow></msup><mo>,</mo></mrow><mrow><mover><mi>y</mi><mo stretchy=\"false\" style=\"math-style:normal;math-depth:0;\">˙</mo></mover><mo>,</mo></mrow><mrow><mover><mi>y</mi><mo stretchy=\"false\" style=\"math-style:normal;math-depth:0;\">¨</mo></mover></mrow></math>"),
     (r"\infty, \aleph, \complement,\backepsilon, \eth, \Finv, \hbar", "<math><mrow><mi>∞</mi><mo>,</mo></mrow><mrow><mi>ℵ</mi><mo>,</mo></mrow><mrow><mi>∁</mi><mo>,</mo></mrow><mrow><mo>∍</mo></mrow><mrow><mo>,</mo></mrow><mrow><mi>ð</mi><mo>,</mo></mrow><mrow><mi>Ⅎ</mi><mo>,</mo></mrow><mrow><mi>ℏ</mi></mrow></math>"),
-    (r"\Im, \imath, \jmath, \Bbbk, \ell, \mho, \wp, \Re, \circledS, \S, \P, \AA", "<math><mrow><mi>ℑ</mi><mo>,</mo></mrow><mrow><mi>ı</mi><mo>,</mo></mrow><mrow><mi>ȷ</mi><mo>,</mo></mrow><mrow><mi>𝕜</mi><mo>,</mo></mrow><mrow><mi>ℓ</mi><mo>,</mo></mrow><mrow><mi>℧</mi><mo>,</mo></mrow><mrow><mi>℘</mi><mo>,</mo></mrow><mrow><mi>ℜ</mi><mo>,</mo></mrow><mrow><mi>Ⓢ</mi><mo>,</mo></mrow><mrow><mi>§</mi><mo>,</mo></mrow><mrow><mi>¶</mi><mo>,</mo></mrow><mrow><mover><mi>A</mi><mo class=\"tml-capshift\" stretchy=\"false\" style=\"math-style:normal;math-depth:0;\">˚</mo></mover></mrow></math>"),
+    (r"\Im, \imath, \jmath, \Bbbk, \ell, \mho, \wp, \Re, \circledS, \S, \P, \text{\AA}", "<math><mrow><mi>ℑ</mi><mo>,</mo></mrow><mrow><mi>ı</mi><mo>,</mo></mrow><mrow><mi>ȷ</mi><mo>,</mo></mrow><mrow><mi>𝕜</mi><mo>,</mo></mrow><mrow><mi>ℓ</mi><mo>,</mo></mrow><mrow><mi>℧</mi><mo>,</mo></mrow><mrow><mi>℘</mi><mo>,</mo></mrow><mrow><mi>ℜ</mi><mo>,</mo></mrow><mrow><mi>Ⓢ</mi><mo>,</mo></mrow><mrow><mi>§</mi><mo>,</mo></mrow><mrow><mi>¶</mi><mo>,</mo></mrow><mrow><mover><mi>A</mi><mo class=\"tml-capshift\" stretchy=\"false\" style=\"math-style:normal;math-depth:0;\">˚</mo></mover></mrow></math>"),
     (r"s_k \equiv 0 \pmod{m}", "<math><mrow><msub><mi>s</mi><mi>k</mi></msub><mo>≡</mo></mrow><mrow><mn>0</mn><mo></mo><mspace width=\"0.4444em\"></mspace><mo form=\"prefix\" stretchy=\"false\">(</mo><mrow><mtext></mtext><mi>mod</mi></mrow><mspace width=\"0.3333em\"></mspace><mi>m</mi><mo form=\"postfix\" stretchy=\"false\">)</mo></mrow></math>"),
     (r"a \bmod b", "<math><mrow><mi>a</mi><mo lspace=\"0.2222em\" rspace=\"0.2222em\">mod</mo></mrow><mrow><mi>b</mi></mrow></math>"),
     (r"\gcd(m, n), \operatorname{lcm}(m, n)", "<math><mrow><mrow><mi>gcd</mi><mo>⁡</mo></mrow><mo form=\"prefix\" stretchy=\"false\">(</mo><mi>m</mi><mo>,</mo><mi>n</mi><mo form=\"postfix\" stretchy=\"false\">)</mo><mo>,</mo></mrow><mrow><mi>lcm</mi><mo>⁡</mo></mrow><mrow><mo form=\"prefix\" stretchy=\"false\">(</mo><mi>m</mi><mo>,</mo><mi>n</mi><mo form=\"postfix\" stretchy=\"false\">)</mo></mrow></math>"),
@@ -47,7 +47,7 @@ fn wiki_test() {
     (r"\oplus, \ominus, \otimes, \oslash, \odot", "<math><mrow><mo>⊕︎</mo></mrow><mrow><mo>,</mo></mrow><mrow><mo form=\"prefix\" stretchy=\"false\">⊖</mo></mrow><mrow><mo>,</mo></mrow><mrow><mo form=\"prefix\" stretchy=\"false\">⊗</mo></mrow><mrow><mo>,</mo></mrow><mrow><mo form=\"prefix\" stretchy=\"false\">⊘</mo></mrow><mrow><mo>,</mo></mrow><mrow><mo form=\"prefix\" stretchy=\"false\">⊙</mo></mrow></math>"),
     (r"\circleddash, \circledcirc, \circledast", "<math><mrow><mo>⊝</mo></mrow><mrow><mo>,</mo></mrow><mrow><mo form=\"prefix\" stretchy=\"false\">⊚</mo></mrow><mrow><mo>,</mo></mrow><mrow><mo form=\"prefix\" stretchy=\"false\">⊛</mo></mrow></math>"),
     (r"\bigoplus, \bigotimes, \bigodot", "<math><mrow><mo movablelimits=\"false\">⨁</mo><mo>,</mo></mrow><mrow><mo movablelimits=\"false\">⨂</mo><mo>,</mo></mrow><mrow><mo movablelimits=\"false\">⨀</mo></mrow></math>"),
-    (r"\{ \}, \O \empty \emptyset, \varnothing", "<math><mrow><mo form=\"prefix\" stretchy=\"false\">{</mo><mo form=\"postfix\" stretchy=\"false\">}</mo><mo>,</mo></mrow><mrow><mi>Ø</mi><mi>∅</mi><mi>∅</mi><mo>,</mo></mrow><mrow><mi>⌀</mi></mrow></math>"),
+    (r"\{ \}, \text{\O} \empty \emptyset, \varnothing", "<math><mrow><mo form=\"prefix\" stretchy=\"false\">{</mo><mo form=\"postfix\" stretchy=\"false\">}</mo><mo>,</mo></mrow><mrow><mi>Ø</mi><mi>∅</mi><mi>∅</mi><mo>,</mo></mrow><mrow><mi>⌀</mi></mrow></math>"),
     (r"\in, \notin \not\in, \ni, \not\ni", "<math><mrow><mo>∈</mo></mrow><mrow><mo>,</mo></mrow><mrow><mo>∉</mo></mrow><mrow><mo>∉</mo></mrow><mrow><mo>,</mo></mrow><mrow><mo>∋</mo></mrow><mrow><mo>,</mo></mrow><mrow><mo>∌</mo></mrow></math>"),
     (r"\cap, \Cap, \sqcap, \bigcap", "<math><mrow><mo>∩</mo></mrow><mrow><mo>,</mo></mrow><mrow><mo form=\"prefix\" stretchy=\"false\">⋒</mo></mrow><mrow><mo>,</mo></mrow><mrow><mo form=\"prefix\" stretchy=\"false\">⊓</mo></mrow><mrow><mo>,</mo></mrow><mrow><mo movablelimits=\"false\">⋂</mo></mrow></math>"),
     (r"\cup, \Cup, \sqcup, \bigcup, \bigsqcup, \uplus, \biguplus", "<math><mrow><mo>∪</mo></mrow><mrow><mo>,</mo></mrow><mrow><mo form=\"prefix\" stretchy=\"false\">⋓</mo></mrow><mrow><mo>,</mo></mrow><mrow><mo form=\"prefix\" stretchy=\"false\">⊔</mo></mrow><mrow><mo>,</mo></mrow><mrow><mo movablelimits=\"false\">⋃</mo><mo>,</mo></mrow><mrow><mo movablelimits=\"false\">⨆</mo><mo>,</mo></mrow><mrow><mo form=\"prefix\" stretchy=\"false\">⊎</mo></mrow><mrow><mo>,</mo></mrow><mrow><mo movablelimits=\"false\">⨄</mo></mrow></math>"),
@@ -263,8 +263,8 @@ fn wiki_test() {
         }
     }
     assert_eq!(n_match, 10);
-    assert_eq!(n_diff, 172);
-    assert_eq!(n_fail, 36);
+    assert_eq!(n_diff, 176);
+    assert_eq!(n_fail, 32);
 }
 
 /// Prettify HTML input
@@ -342,13 +342,16 @@ fn test_nonfailing_wiki_tests() {
         ),
         (
             25,
-            r"\Im, \imath, \jmath, \Bbbk, \ell, \mho, \wp, \Re, \circledS, \S, \P, \AA",
+            r"\Im, \imath, \jmath, \Bbbk, \ell, \mho, \wp, \Re, \circledS, \S, \P, \text{\AA}",
         ),
         (26, r"s_k \equiv 0 \pmod{m}"),
         (27, r"a \bmod b"),
         (28, r"\gcd(m, n), \operatorname{lcm}(m, n)"),
         // (29, r"\mid, \nmid, \shortmid, \nshortmid"),
-        // (30, r"\surd, \sqrt{2}, \sqrt[n]{2}, \sqrt[3]{\frac{x^3+y^3}{2}}"),
+        (
+            30,
+            r"\surd, \sqrt{2}, \sqrt[n]{2}, \sqrt[3]{\frac{x^3+y^3}{2}}",
+        ),
         (31, r"+, -, \pm, \mp, \dotplus"),
         (32, r"\times, \div, \divideontimes, /, \backslash"),
         (33, r"\cdot, * \ast, \star, \circ, \bullet"),
@@ -356,7 +359,7 @@ fn test_nonfailing_wiki_tests() {
         (35, r"\oplus, \ominus, \otimes, \oslash, \odot"),
         (36, r"\circleddash, \circledcirc, \circledast"),
         (37, r"\bigoplus, \bigotimes, \bigodot"),
-        (38, r"\{ \}, \O \empty \emptyset, \varnothing"),
+        (38, r"\{ \}, \text{\O} \empty \emptyset, \varnothing"),
         (39, r"\in, \notin \not\in, \ni, \not\ni"),
         (40, r"\cap, \Cap, \sqcap, \bigcap"),
         (
@@ -688,10 +691,10 @@ fn test_nonfailing_wiki_tests() {
         (199, r"| {\uparrow} \rangle"),
         // (200, r"| \mathord\uparrow \rangle"),
         // (201, r"\wideparen{AB}"),
-        // (202, r"\dddot{x}"),
+        (202, r"\dddot{x}"),
         // (203, r"\sout{q}"),
         // (204, r"\mathrlap{\,/}{=}"),
-        // (205, r"\text{\textsf{textual description}}"),
+        (205, r"\text{\textsf{textual description}}"),
         (206, r"α π"),
         (207, r"ax^2 + bx + c = 0"),
         (208, r"x=\frac{-b\pm\sqrt{b^2-4ac}}{2a}"),
@@ -723,8 +726,31 @@ fn test_nonfailing_wiki_tests() {
 
     for (num, problem) in problems.into_iter() {
         let mathml = latex_to_mathml(problem, crate::Display::Inline, true)
-            .expect(format!("failed to convert `{}`", problem).as_str());
+            .unwrap_or_else(|_| panic!("failed to convert `{}`", problem));
         let name = format!("wiki{:03}", num);
         assert_snapshot!(name.as_str(), &mathml, problem);
     }
 }
+
+#[test]
+fn lenient_wiki_tests() {
+    // The reference output accepts the text-mode commands `\AA` and `\O` in math mode.
+    let problems = [
+        (
+            25,
+            r"\Im, \imath, \jmath, \Bbbk, \ell, \mho, \wp, \Re, \circledS, \S, \P, \AA",
+        ),
+        (38, r"\{ \}, \O \empty \emptyset, \varnothing"),
+    ];
+    let config = Config {
+        lenient_modes: true,
+        ..Default::default()
+    };
+
+    for (num, problem) in problems.into_iter() {
+        let mathml = latex_to_mathml_with_config(problem, crate::Display::Inline, true, &config)
+            .unwrap_or_else(|_| panic!("failed to convert `{}`", problem));
+        let name = format!("wiki{:03}_lenient", num);
+        assert_snapshot!(name.as_str(), &mathml, problem);
+    }
+}
@@ -28,7 +28,10 @@ use std::{
 
 use clap::Parser;
 
-use latex2mmlc::{latex_to_mathml, Display};
+use latex2mmlc::{
+    latex_to_mathml, latex_to_mathml_with_warnings, Config, Display, MathStyle, NumberFormat,
+    OperatorLocale,
+};
 
 use crate::replace::{ConversionError, Replacer};
 
@@ -112,6 +115,71 @@ struct Args {
     /// Sets the display style for the formula to "block"
     #[arg(short, long, conflicts_with = "file", group = "mode")]
     block: bool,
+
+    /// Splits an inline formula after relations and binary operators and at `\\` to allow line breaks
+    #[arg(long, conflicts_with_all = ["file", "block"])]
+    line_breaks: bool,
+
+    /// Renders \mathcal and \mathscr as plain script letters without variation selectors
+    #[arg(long, conflicts_with = "file")]
+    plain_script: bool,
+
+    /// Sets the letter shapes to those of a unicode-math math-style preset
+    #[arg(long, value_enum, default_value_t = StyleArg::Tex, conflicts_with = "file")]
+    math_style: StyleArg,
+
+    /// Renders the differential d and the constants e, i and π upright
+    #[arg(long, conflicts_with = "file")]
+    upright_constants: bool,
+
+    /// Lays out the formula from right to left
+    #[arg(long, conflicts_with = "file")]
+    rtl: bool,
+
+    /// Accepts the command NAME as an operator with limits, like \argmax (repeatable)
+    #[arg(long = "limit-operator", value_name = "NAME", conflicts_with = "file")]
+    limit_operators: Vec<String>,
+
+    /// Accepts text-mode commands like \AA in math and math commands like \alpha in text,
+    /// with a warning
+    #[arg(long = "lenient-modes", conflicts_with = "file")]
+    lenient_modes: bool,
+
+    /// Uses the comma as the decimal separator and the full stop for grouping digits
+    #[arg(long, conflicts_with = "file")]
+    decimal_comma: bool,
+
+    /// Continues numbers after separators in braces, as in 3{,}14, and thin spaces, as in 1\,000
+    #[arg(long, conflicts_with = "file")]
+    braced_separators: bool,
+
+    /// Groups the digits of long numbers in threes with thin spaces
+    #[arg(long, conflicts_with = "file")]
+    group_digits: bool,
+
+    /// Adds the function names of a language, like \sen and \tg in Spanish
+    #[arg(long, value_enum, default_value_t = LocaleArg::English, conflicts_with = "file")]
+    operator_locale: LocaleArg,
+
+    /// Displays \sin, \tan, ... with the names of the operator locale
+    #[arg(long, conflicts_with = "file")]
+    localize_standard_operators: bool,
+}
+
+#[derive(Clone, Copy, Debug, clap::ValueEnum)]
+enum StyleArg {
+    Tex,
+    Iso,
+    French,
+    Upright,
+}
+
+#[derive(Clone, Copy, Debug, clap::ValueEnum)]
+enum LocaleArg {
+    English,
+    French,
+    Russian,
+    Spanish,
 }
 
 fn main() {
@@ -162,8 +230,45 @@ fn convert_and_exit(args: &Args, latex: &str) {
     } else {
         Display::Inline
     };
-    match latex_to_mathml(latex, display, false) {
-        Ok(mathml) => println!("{}", mathml),
+    let mut number_format = NumberFormat {
+        braced_separators: args.braced_separators,
+        thin_space_groups: args.braced_separators,
+        group_digits: args.group_digits,
+        ..Default::default()
+    };
+    if args.decimal_comma {
+        number_format.decimal_separator = ',';
+        number_format.group_separator = Some('.');
+    }
+    let config = Config {
+        inline_line_breaks: args.line_breaks,
+        plain_script: args.plain_script,
+        math_style: match args.math_style {
+            StyleArg::Tex => MathStyle::TeX,
+            StyleArg::Iso => MathStyle::Iso,
+            StyleArg::French => MathStyle::French,
+            StyleArg::Upright => MathStyle::Upright,
+        },
+        upright_constants: args.upright_constants,
+        right_to_left: args.rtl,
+        limit_operators: args.limit_operators.clone(),
+        lenient_modes: args.lenient_modes,
+        number_format,
+        operator_locale: match args.operator_locale {
+            LocaleArg::English => OperatorLocale::English,
+            LocaleArg::French => OperatorLocale::French,
+            LocaleArg::Russian => OperatorLocale::Russian,
+            LocaleArg::Spanish => OperatorLocale::Spanish,
+        },
+        localize_standard_operators: args.localize_standard_operators,
+    };
+    match latex_to_mathml_with_warnings(latex, display, false, &config) {
+        Ok((mathml, warnings)) => {
+            for warning in warnings {
+                eprintln!("LaTeX2MathML Warning: {}", warning);
+            }
+            println!("{}", mathml)
+        }
         Err(e) => exit_latex_error(e),
     }
 }
@@ -45,6 +45,20 @@ impl Arena {
         self.bump.alloc_slice_copy(nodes)
     }
 
+    #[cfg(target_arch = "wasm32")]
+    #[inline]
+    pub fn push_items<'arena, T: Copy>(&'arena self, items: &[T]) -> &'arena [T] {
+        // This fails if the bump allocator is out of memory.
+        self.bump
+            .try_alloc_slice_copy(items)
+            .unwrap_or_else(|_| std::process::abort())
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    #[inline]
+    pub fn push_items<'arena, T: Copy>(&'arena self, items: &[T]) -> &'arena [T] {
+        self.bump.alloc_slice_copy(items)
+    }
+
     fn alloc_str(&self, src: &str) -> &str {
         self.bump
             .try_alloc_str(src)
@@ -3,8 +3,10 @@ use std::mem;
 #[cfg(feature = "serde")]
 use serde::Serialize;
 
+use crate::arena::Arena;
 use crate::attribute::{
-    Align, FracAttr, MathSpacing, MathVariant, OpAttr, Size, StretchMode, Stretchy, Style,
+    Align, ColumnAlign, ColumnSpec, FontSize, FracAttr, MathSpacing, MathVariant, OpAttr, Size,
+    StretchMode, Stretchy, Style, TextTransform,
 };
 use crate::ops::{Op, ParenOp};
 
@@ -25,6 +27,8 @@ pub enum Node<'arena> {
         right: Option<MathSpacing>,
     },
     MultiLetterIdent(&'arena str),
+    /// An operator name like `argmax` in an `<mo>`, so that it can have movable limits.
+    MultiLetterOperator(&'arena str, Option<OpAttr>),
     CollectedLetters(&'arena str),
     Space(&'static str),
     Subscript {
@@ -57,19 +61,32 @@ pub enum Node<'arena> {
     },
     Sqrt(&'arena Node<'arena>),
     Root(&'arena Node<'arena>, &'arena Node<'arena>),
+    /// Content moved left and up by a number of mu, like the index of a root
+    /// after `\leftroot` and `\uproot`.
+    Shifted {
+        content: &'arena Node<'arena>,
+        left: i16,
+        up: i16,
+    },
     Frac {
         /// Numerator
         num: &'arena Node<'arena>,
         /// Denominator
         den: &'arena Node<'arena>,
-        /// Line thickness
-        lt: Option<char>,
+        /// Line thickness, a length with its unit, such as `0pt` or `1.5pt`.
+        ///
+        /// The extra reference keeps the node small on 32-bit targets.
+        lt: Option<&'arena &'arena str>,
         attr: Option<FracAttr>,
     },
     Row {
         nodes: &'arena [&'arena Node<'arena>],
         style: Option<Style>,
     },
+    SizedRow {
+        nodes: &'arena [&'arena Node<'arena>],
+        size: FontSize,
+    },
     Fenced {
         style: Option<Style>,
         open: &'static ParenOp,
@@ -79,12 +96,21 @@ pub enum Node<'arena> {
     SizedParen(Size, &'static ParenOp),
     Text(&'arena str),
     Table {
-        content: &'arena [&'arena Node<'arena>],
+        rows: &'arena [TableRow<'arena>],
         align: Align,
         attr: Option<FracAttr>,
     },
     ColumnSeparator,
     RowSeparator,
+    /// A cell which spans several columns, from `\multicolumn` or `\hdotsfor`.
+    /// This only takes effect at the start of a table cell.
+    MultiColumn {
+        columns: u16,
+        spec: Option<ColumnSpec>,
+        content: &'arena Node<'arena>,
+    },
+    /// A horizontal rule above the current table row, from `\hline` or `\cline`.
+    HorizontalRule(RuleSpan),
     Slashed(&'arena Node<'arena>),
     Multiscript {
         base: &'arena Node<'arena>,
@@ -102,6 +128,99 @@ pub enum Node<'arena> {
     HardcodedMathML(&'static str),
 }
 
+/// A row of a [`Node::Table`].
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct TableRow<'arena> {
+    pub cells: &'arena [TableCell<'arena>],
+    /// The horizontal rules above the row.
+    pub rules: &'arena [RuleSpan],
+}
+
+/// A cell of a [`TableRow`].
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct TableCell<'arena> {
+    pub content: &'arena [&'arena Node<'arena>],
+    /// The number of columns that the cell spans.
+    pub columnspan: u16,
+    /// The alignment and the vertical rules of the cell, if they were specified.
+    pub spec: Option<ColumnSpec>,
+}
+
+/// The columns covered by a horizontal rule.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub enum RuleSpan {
+    /// All columns, as with `\hline`.
+    Full,
+    /// The columns from the first to the last one, counted from 1, as with `\cline{2-3}`.
+    Columns(u16, u16),
+}
+
+impl RuleSpan {
+    /// Whether the rule covers any of the columns `first..=last`.
+    fn covers(&self, first: usize, last: usize) -> bool {
+        match *self {
+            RuleSpan::Full => true,
+            RuleSpan::Columns(start, end) => {
+                usize::from(start) <= last && first <= usize::from(end)
+            }
+        }
+    }
+}
+
+/// Split the content of a table at its `ColumnSeparator` and `RowSeparator` markers
+/// into rows of cells.
+///
+/// `columns` is the column specification of an `array`; it is empty for the other
+/// environments.
+pub fn table_rows<'arena>(
+    arena: &'arena Arena,
+    content: &'arena [&'arena Node<'arena>],
+    columns: &[ColumnSpec],
+) -> &'arena [TableRow<'arena>] {
+    let mut rows = Vec::new();
+    for row in content.split(|node| matches!(node, Node::RowSeparator)) {
+        let mut cells = Vec::new();
+        let mut rules = Vec::new();
+        let mut column = 0usize;
+        for cell in row.split(|node| matches!(node, Node::ColumnSeparator)) {
+            let mut content = cell;
+            if cell
+                .iter()
+                .any(|node| matches!(node, Node::HorizontalRule(_)))
+            {
+                let mut nodes = Vec::with_capacity(cell.len());
+                for node in cell.iter() {
+                    match node {
+                        Node::HorizontalRule(span) => rules.push(*span),
+                        node => nodes.push(*node),
+                    }
+                }
+                content = arena.push_slice(&nodes);
+            }
+            let (columnspan, spec) = match content.first() {
+                Some(Node::MultiColumn {
+                    columns: n, spec, ..
+                }) => (*n, spec.or(columns.get(column).copied())),
+                _ => (1, columns.get(column).copied()),
+            };
+            column += usize::from(columnspan);
+            cells.push(TableCell {
+                content,
+                columnspan,
+                spec,
+            });
+        }
+        rows.push(TableRow {
+            cells: arena.push_items(&cells),
+            rules: arena.push_items(&rules),
+        });
+    }
+    arena.push_items(&rows)
+}
+
 impl PartialEq for &'static Node<'static> {
     fn eq(&self, other: &&'static Node<'static>) -> bool {
         std::ptr::eq(*self, *other)
@@ -137,7 +256,11 @@ macro_rules! pushln {
 pub struct MathMLEmitter<'arena> {
     s: String,
     var: Option<MathVariant>,
+    /// The font size of the enclosing size command.
+    font_size: FontSize,
     custom_cmd_args: Option<&'arena [&'arena Node<'arena>]>,
+    /// Whether to append the variation selectors of `\mathcal` and `\mathscr`.
+    variation_selectors: bool,
 }
 
 impl<'arena> MathMLEmitter<'arena> {
@@ -146,10 +269,19 @@ impl<'arena> MathMLEmitter<'arena> {
         Self {
             s: String::new(),
             var: None,
+            font_size: FontSize::NormalSize,
             custom_cmd_args: None,
+            variation_selectors: true,
         }
     }
 
+    /// Render `\mathcal` and `\mathscr` as plain script letters, for fonts which
+    /// lack the chancery and roundhand variants.
+    #[inline]
+    pub fn use_plain_script(&mut self) {
+        self.variation_selectors = false;
+    }
+
     #[inline]
     pub fn into_inner(self) -> String {
         self.s
@@ -187,6 +319,9 @@ impl<'arena> MathMLEmitter<'arena> {
             node,
             Node::ColumnSeparator
                 | Node::RowSeparator
+                | Node::HardcodedMathML("")
+                | Node::HorizontalRule(_)
+                | Node::MultiColumn { .. }
                 | Node::TextTransform { .. }
                 | Node::CustomCmd { .. }
                 | Node::CustomCmdArg(_)
@@ -200,8 +335,9 @@ impl<'arena> MathMLEmitter<'arena> {
                 if let Some(MathVariant::Transform(tf)) = self.var {
                     // We render transformed numbers as identifiers.
                     push!(self.s, "<mi>");
-                    self.s
-                        .extend(number.chars().map(|c| tf.transform(c, false)));
+                    for c in number.chars() {
+                        self.push_transformed(tf, c, false);
+                    }
                     push!(self.s, "</mi>");
                 } else {
                     push!(self.s, "<mn>", number, "</mn>");
@@ -217,14 +353,19 @@ impl<'arena> MathMLEmitter<'arena> {
                 } else {
                     push!(self.s, "<mi>");
                 }
-                let c = match self.var {
-                    Some(MathVariant::Transform(tf)) => tf.transform(*letter, is_normal),
-                    _ => *letter,
+                match self.var {
+                    Some(MathVariant::Transform(tf)) => {
+                        self.push_transformed(tf, *letter, is_normal)
+                    }
+                    _ => self.s.push(*letter),
                 };
-                push!(self.s, @c, "</mi>");
+                push!(self.s, "</mi>");
             }
             Node::TextTransform { content, tf } => {
-                let old_var = mem::replace(&mut self.var, Some(*tf));
+                let old_var = match tf {
+                    MathVariant::Reset => self.var.take(),
+                    _ => self.var.replace(*tf),
+                };
                 self.emit(content, base_indent);
                 self.var = old_var;
             }
@@ -269,6 +410,13 @@ impl<'arena> MathMLEmitter<'arena> {
             Node::MultiLetterIdent(letters) => {
                 push!(self.s, "<mi>", letters, "</mi>");
             }
+            Node::MultiLetterOperator(letters, attributes) => {
+                match attributes {
+                    Some(attributes) => push!(self.s, "<mo", attributes, ">"),
+                    None => push!(self.s, "<mo>"),
+                }
+                push!(self.s, letters, "</mo>");
+            }
             node @ (Node::CollectedLetters(letters) | Node::Text(letters)) => {
                 let (open, close) = match node {
                     Node::CollectedLetters(_) => ("<mi>", "</mi>"),
@@ -278,9 +426,11 @@ impl<'arena> MathMLEmitter<'arena> {
                 };
                 push!(self.s, open);
                 match self.var {
-                    Some(MathVariant::Transform(tf)) => self
-                        .s
-                        .extend(letters.chars().map(|c| tf.transform(c, false))),
+                    Some(MathVariant::Transform(tf)) => {
+                        for c in letters.chars() {
+                            self.push_transformed(tf, c, false);
+                        }
+                    }
                     _ => self.s.push_str(letters),
                 }
                 push!(self.s, close);
@@ -341,6 +491,22 @@ impl<'arena> MathMLEmitter<'arena> {
                 self.emit(third, child_indent);
                 pushln!(&mut self.s, base_indent, close);
             }
+            Node::Shifted { content, left, up } => {
+                push!(self.s, "<mpadded");
+                if *left != 0 {
+                    push!(self.s, " lspace=\"");
+                    push_mu_as_em(&mut self.s, -i32::from(*left));
+                    push!(self.s, "\"");
+                }
+                if *up != 0 {
+                    push!(self.s, " voffset=\"");
+                    push_mu_as_em(&mut self.s, i32::from(*up));
+                    push!(self.s, "\"");
+                }
+                push!(self.s, ">");
+                self.emit(content, child_indent);
+                pushln!(&mut self.s, base_indent, "</mpadded>");
+            }
             Node::Multiscript { base, sub } => {
                 push!(self.s, "<mmultiscripts>");
                 self.emit(base, child_indent);
@@ -373,7 +539,7 @@ impl<'arena> MathMLEmitter<'arena> {
             Node::Frac { num, den, lt, attr } => {
                 push!(self.s, "<mfrac");
                 if let Some(lt) = lt {
-                    push!(self.s, " linethickness=\"", @*lt, "pt\"");
+                    push!(self.s, " linethickness=\"", **lt, "\"");
                 }
                 if let Some(style) = attr {
                     push!(self.s, style);
@@ -393,6 +559,20 @@ impl<'arena> MathMLEmitter<'arena> {
                 }
                 pushln!(&mut self.s, base_indent, "</mrow>");
             }
+            Node::SizedRow { nodes, size } => {
+                // Size commands are absolute in LaTeX, but CSS sizes are
+                // relative to the parent, so we divide by the enclosing size.
+                let ratio = size.scale() / self.font_size.scale();
+                push!(self.s, "<mrow style=\"font-size: ");
+                push_thousandths(&mut self.s, (ratio * 1000.0).round() as u32);
+                push!(self.s, "em\">");
+                let old_size = mem::replace(&mut self.font_size, *size);
+                for node in nodes.iter() {
+                    self.emit(node, child_indent);
+                }
+                self.font_size = old_size;
+                pushln!(&mut self.s, base_indent, "</mrow>");
+            }
             Node::Fenced {
                 open,
                 close,
@@ -430,11 +610,7 @@ impl<'arena> MathMLEmitter<'arena> {
                 }
                 n => self.emit(n, base_indent),
             },
-            Node::Table {
-                content,
-                align,
-                attr,
-            } => {
+            Node::Table { rows, align, attr } => {
                 let child_indent2 = if base_indent > 0 {
                     child_indent.saturating_add(1)
                 } else {
@@ -446,60 +622,119 @@ impl<'arena> MathMLEmitter<'arena> {
                     0
                 };
                 let odd_col = match align {
-                    Align::Center => "<mtd>",
+                    Align::Center => None,
                     Align::Left => {
-                        r#"<mtd style="text-align: -webkit-left; text-align: -moz-left; padding-right: 0">"#
+                        Some("text-align: -webkit-left; text-align: -moz-left; padding-right: 0")
                     }
                     Align::Alternating => {
-                        r#"<mtd style="text-align: -webkit-right; text-align: -moz-right; padding-right: 0">"#
+                        Some("text-align: -webkit-right; text-align: -moz-right; padding-right: 0")
                     }
                 };
                 let even_col = match align {
-                    Align::Center => "<mtd>",
-                    Align::Left => {
-                        "<mtd style=\"text-align: -webkit-left; text-align: -moz-left; padding-right: 0; padding-left: 1em\">"
+                    Align::Center => None,
+                    Align::Left => Some(
+                        "text-align: -webkit-left; text-align: -moz-left; padding-right: 0; padding-left: 1em",
+                    ),
+                    Align::Alternating => {
+                        Some("text-align: -webkit-left; text-align: -moz-left; padding-left: 0")
                     }
-                    Align::Alternating => "<mtd style=\"text-align: -webkit-left; text-align: -moz-left; padding-left: 0\">",
                 };
 
-                let mut col: usize = 1;
+                // A last row with nothing but rules (as in `a \\ \hline`) draws
+                // them below the row before it.
+                let mut rows: &[TableRow] = rows;
+                let mut rules_below: &[RuleSpan] = &[];
+                if let [init @ .., _, last] = rows {
+                    if let [cell] = last.cells {
+                        if cell.content.is_empty() && !last.rules.is_empty() {
+                            rules_below = last.rules;
+                            rows = &rows[..init.len() + 1];
+                        }
+                    }
+                }
+
                 push!(self.s, "<mtable");
                 if let Some(attr) = attr {
                     push!(self.s, attr);
                 }
                 push!(self.s, ">");
-                pushln!(&mut self.s, child_indent, "<mtr>");
-                pushln!(&mut self.s, child_indent2, odd_col);
-                for node in content.iter() {
-                    match node {
-                        Node::ColumnSeparator => {
-                            pushln!(&mut self.s, child_indent2, "</mtd>");
-                            col += 1;
-                            pushln!(
-                                &mut self.s,
-                                child_indent2,
-                                if col % 2 == 0 { even_col } else { odd_col }
-                            );
+                for (i, row) in rows.iter().enumerate() {
+                    let rules_below = if i + 1 == rows.len() {
+                        rules_below
+                    } else {
+                        &[]
+                    };
+                    pushln!(&mut self.s, child_indent, "<mtr>");
+                    let mut col: usize = 1;
+                    for cell in row.cells.iter() {
+                        let last_col = col + usize::from(cell.columnspan).max(1) - 1;
+                        pushln!(&mut self.s, child_indent2, "<mtd");
+                        if cell.columnspan > 1 {
+                            push!(self.s, " columnspan=\"");
+                            push_number(&mut self.s, u32::from(cell.columnspan));
+                            push!(self.s, "\"");
+                        }
+                        // The separator before the next declaration, which opens the
+                        // attribute for the first one.
+                        let mut sep = " style=\"";
+                        let mut push_style = |s: &mut String, decl: &str| {
+                            push!(s, sep, decl);
+                            sep = "; ";
+                        };
+                        match cell.spec {
+                            Some(spec) => {
+                                push_style(
+                                    &mut self.s,
+                                    match spec.align {
+                                        ColumnAlign::Left => {
+                                            "text-align: -webkit-left; text-align: -moz-left"
+                                        }
+                                        ColumnAlign::Center => "text-align: center",
+                                        ColumnAlign::Right => {
+                                            "text-align: -webkit-right; text-align: -moz-right"
+                                        }
+                                    },
+                                );
+                                if spec.line_before {
+                                    push_style(&mut self.s, "border-left: 0.05em solid");
+                                }
+                                if spec.line_after {
+                                    push_style(&mut self.s, "border-right: 0.05em solid");
+                                }
+                            }
+                            None => {
+                                if let Some(align) = if col % 2 == 0 { even_col } else { odd_col } {
+                                    push_style(&mut self.s, align);
+                                }
+                            }
+                        }
+                        if row.rules.iter().any(|rule| rule.covers(col, last_col)) {
+                            push_style(&mut self.s, "border-top: 0.05em solid");
                         }
-                        Node::RowSeparator => {
-                            pushln!(&mut self.s, child_indent2, "</mtd>");
-                            pushln!(&mut self.s, child_indent, "</mtr>");
-                            pushln!(&mut self.s, child_indent, "<mtr>");
-                            pushln!(&mut self.s, child_indent2, odd_col);
-                            col = 1;
+                        if rules_below.iter().any(|rule| rule.covers(col, last_col)) {
+                            push_style(&mut self.s, "border-bottom: 0.05em solid");
                         }
-                        node => {
+                        if sep == "; " {
+                            push!(self.s, "\"");
+                        }
+                        push!(self.s, ">");
+                        for node in cell.content.iter() {
                             self.emit(node, child_indent3);
                         }
+                        pushln!(&mut self.s, child_indent2, "</mtd>");
+                        col = last_col + 1;
                     }
+                    pushln!(&mut self.s, child_indent, "</mtr>");
                 }
-                pushln!(&mut self.s, child_indent2, "</mtd>");
-                pushln!(&mut self.s, child_indent, "</mtr>");
                 pushln!(&mut self.s, base_indent, "</mtable>");
             }
+            Node::MultiColumn { content, .. } => self.emit(content, base_indent),
+            Node::HorizontalRule(_) => (),
+            // Outside of a table, MathML Core can't represent a line break; top-level breaks
+            // are handled by splitting the formula before it gets here.
             Node::ColumnSeparator | Node::RowSeparator => (),
             Node::CustomCmd { predefined, args } => {
-                let old_args = mem::replace(&mut self.custom_cmd_args, Some(args));
+                let old_args = self.custom_cmd_args.replace(args);
                 self.emit(predefined, base_indent);
                 self.custom_cmd_args = old_args;
             }
@@ -518,6 +753,15 @@ impl<'arena> MathMLEmitter<'arena> {
         }
     }
 
+    fn push_transformed(&mut self, tf: TextTransform, c: char, is_normal: bool) {
+        self.s.push(tf.transform(c, is_normal));
+        if self.variation_selectors {
+            if let Some(selector) = tf.variation_selector(c) {
+                self.s.push(selector);
+            }
+        }
+    }
+
     fn emit_stretchy_op(&mut self, stretch_mode: StretchMode, op: &ParenOp) {
         match (stretch_mode, op.stretchy()) {
             (StretchMode::Fence, Stretchy::Never | Stretchy::Inconsistent)
@@ -557,10 +801,51 @@ fn new_line_and_indent(s: &mut String, indent_num: usize) {
     }
 }
 
+/// Write a number in decimal digits.
+fn push_number(s: &mut String, n: u32) {
+    if n >= 10 {
+        push_number(s, n / 10);
+    }
+    s.push(char::from(b'0' + (n % 10) as u8));
+}
+
+/// Write a number given in thousandths, without trailing zeros.
+fn push_thousandths(s: &mut String, thousandths: u32) {
+    push_number(s, thousandths / 1000);
+    let mut frac = thousandths % 1000;
+    if frac != 0 {
+        s.push('.');
+        let mut digit = 100;
+        while frac != 0 {
+            s.push(char::from(b'0' + (frac / digit) as u8));
+            frac %= digit;
+            digit /= 10;
+        }
+    }
+}
+
+/// Write a length in mu (1/18 em) in em, truncated to three decimals.
+fn push_mu_as_em(s: &mut String, mu: i32) {
+    let thousandths = mu * 1000 / 18;
+    if thousandths < 0 {
+        s.push('-');
+    }
+    let thousandths = thousandths.unsigned_abs();
+    push_number(s, thousandths / 1000);
+    s.push('.');
+    for digit in [100, 10, 1] {
+        s.push(char::from(b'0' + (thousandths / digit % 10) as u8));
+    }
+    s.push_str("em");
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{MathMLEmitter, Node};
-    use crate::attribute::{FracAttr, MathSpacing, MathVariant, OpAttr, Style, TextTransform};
+    use super::{table_rows, MathMLEmitter, Node};
+    use crate::arena::Arena;
+    use crate::attribute::{
+        FontSize, FracAttr, MathSpacing, MathVariant, OpAttr, Style, TextTransform,
+    };
     use crate::ops;
 
     pub fn render<'a, 'b>(node: &'a Node<'b>) -> String
@@ -697,7 +982,7 @@ mod tests {
     fn render_over_op() {
         assert_eq!(
             render(&Node::OverOp(
-                ops::MACRON.into(),
+                ops::MACRON,
                 Some(OpAttr::StretchyFalse),
                 &Node::SingleLetterIdent('x', false),
             )),
@@ -717,7 +1002,7 @@ mod tests {
     fn render_under_op() {
         assert_eq!(
             render(&Node::UnderOp(
-                ops::LOW_LINE.into(),
+                ops::LOW_LINE,
                 &Node::SingleLetterIdent('x', false),
             )),
             "<munder><mi>x</mi><mo accent=\"true\">_</mo></munder>"
@@ -794,7 +1079,7 @@ mod tests {
             render(&Node::Frac {
                 num,
                 den,
-                lt: Some('1'),
+                lt: Some(&"1pt"),
                 attr: None,
             }),
             "<mfrac linethickness=\"1pt\"><mn>1</mn><mn>2</mn></mfrac>"
@@ -812,7 +1097,7 @@ mod tests {
             render(&Node::Frac {
                 num,
                 den,
-                lt: Some('0'),
+                lt: Some(&"0pt"),
                 attr: Some(FracAttr::DisplayStyleTrue),
             }),
             "<mfrac linethickness=\"0pt\" displaystyle=\"true\"><mn>1</mn><mn>2</mn></mfrac>"
@@ -845,6 +1130,28 @@ mod tests {
         );
     }
 
+    #[test]
+    fn render_sized_row() {
+        let nodes = &[&Node::SingleLetterIdent('x', false)];
+
+        assert_eq!(
+            render(&Node::SizedRow {
+                nodes,
+                size: FontSize::Large
+            }),
+            "<mrow style=\"font-size: 1.2em\"><mi>x</mi></mrow>"
+        );
+    }
+
+    #[test]
+    fn push_thousandths_test() {
+        for (thousandths, expected) in [(0, "0"), (1200, "1.2"), (833, "0.833"), (2050, "2.05")] {
+            let mut s = String::new();
+            super::push_thousandths(&mut s, thousandths);
+            assert_eq!(s, expected);
+        }
+    }
+
     #[test]
     fn render_hardcoded_mathml() {
         assert_eq!(render(&Node::HardcodedMathML("<mi>hi</mi>")), "<mi>hi</mi>");
@@ -885,9 +1192,11 @@ mod tests {
             &Node::Number("4"),
         ];
 
+        let arena = Arena::new();
+        let content = arena.push_slice(&nodes);
         assert_eq!(
             render(&Node::Table {
-                content: &nodes,
+                rows: table_rows(&arena, content, &[]),
                 align: crate::attribute::Align::Center,
                 attr: None,
             }),
@@ -903,6 +1212,26 @@ mod tests {
         );
     }
 
+    #[test]
+    fn render_shifted() {
+        assert_eq!(
+            render(&Node::Shifted {
+                content: &Node::SingleLetterIdent('n', false),
+                left: -2,
+                up: 18,
+            }),
+            "<mpadded lspace=\"0.111em\" voffset=\"1.000em\"><mi>n</mi></mpadded>"
+        );
+        assert_eq!(
+            render(&Node::Shifted {
+                content: &Node::SingleLetterIdent('n', false),
+                left: i16::MIN,
+                up: i16::MAX,
+            }),
+            "<mpadded lspace=\"1820.444em\" voffset=\"1820.388em\"><mi>n</mi></mpadded>"
+        );
+    }
+
     #[test]
     fn render_multiscript() {
         assert_eq!(
@@ -11,6 +11,8 @@ pub enum MathVariant {
     Normal,
     /// This is enforced by transforming the characters themselves.
     Transform(TextTransform),
+    /// Go back to the default letter shapes, undoing any enclosing variant.
+    Reset,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, AsRefStr)]
@@ -20,8 +22,15 @@ pub enum OpAttr {
     StretchyFalse,
     #[strum(serialize = r#" movablelimits="false""#)]
     NoMovableLimits,
+    #[strum(serialize = r#" movablelimits="true""#)]
+    MovableLimits,
     #[strum(serialize = r#" form="prefix""#)]
     FormPrefix,
+    /// The spacing of `lim` for the names which are not in the operator dictionary.
+    #[strum(serialize = r#" lspace="0" rspace="0.1667em""#)]
+    NameSpacing,
+    #[strum(serialize = r#" lspace="0" rspace="0.1667em" movablelimits="true""#)]
+    NameSpacingMovableLimits,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, AsRefStr)]
@@ -37,6 +46,40 @@ pub enum Size {
     Scale4,
 }
 
+/// Font size switches like `\large`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub enum FontSize {
+    Tiny = 1,
+    ScriptSize,
+    FootnoteSize,
+    Small,
+    NormalSize,
+    Large,
+    Large2,
+    Large3,
+    Huge,
+    Huge2,
+}
+
+impl FontSize {
+    /// The size relative to `\normalsize`, following the 10pt LaTeX classes.
+    pub fn scale(self) -> f32 {
+        match self {
+            FontSize::Tiny => 0.5,
+            FontSize::ScriptSize => 0.7,
+            FontSize::FootnoteSize => 0.8,
+            FontSize::Small => 0.9,
+            FontSize::NormalSize => 1.0,
+            FontSize::Large => 1.2,
+            FontSize::Large2 => 1.44,
+            FontSize::Large3 => 1.728,
+            FontSize::Huge => 2.074,
+            FontSize::Huge2 => 2.488,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize))]
 pub enum StretchMode {
@@ -94,6 +137,26 @@ pub enum Align {
     Alternating,
 }
 
+/// The alignment of a table column, as given by `l`, `c` or `r`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub enum ColumnAlign {
+    Left,
+    Center,
+    Right,
+}
+
+/// One column of a column specification like `{c|l}`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct ColumnSpec {
+    pub align: ColumnAlign,
+    /// A vertical rule on the left of the column.
+    pub line_before: bool,
+    /// A vertical rule on the right of the column.
+    pub line_after: bool,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, AsRefStr)]
 #[cfg_attr(feature = "serde", derive(Serialize))]
 pub enum MathSpacing {
@@ -112,18 +175,141 @@ pub enum TextTransform {
     BoldItalic,
     BoldSansSerif,
     BoldScript,
+    /// Bold script letters in the chancery style of `\mathbfcal`.
+    BoldScriptChancery,
+    /// Bold script letters in the roundhand style of `\mathbfscr`.
+    BoldScriptRoundhand,
+    /// Bold in the style of unicode-math's `bold-style=TeX`: Latin letters are
+    /// upright, while Greek letters are italic unless they are upright anyway.
+    BoldUprightLatin,
     DoubleStruck,
+    /// Bold italic for all letters, including those that are usually upright,
+    /// as in `\mathbfit`.
+    ExplicitBoldItalic,
     Fraktur,
-    // Initial,
+    Initial,
     Italic,
-    // Looped,
+    Looped,
     Monospace,
     SansSerif,
     SansSerifBoldItalic,
+    /// The sans-serif version of [`TextTransform::BoldUprightLatin`].
+    SansSerifBoldUprightLatin,
     SansSerifItalic,
     Script,
-    // Stretched,
-    // Tailed,
+    /// Script letters in the chancery style of `\mathcal`.
+    ScriptChancery,
+    /// Script letters in the roundhand style of `\mathscr`.
+    ScriptRoundhand,
+    Stretched,
+    Tailed,
+}
+
+/// A change of the text font, as done by commands like `\textbf` or `\emph`.
+///
+/// Unlike the math alphabets, text font changes compose: `\textbf{\textit{x}}`
+/// is bold italic.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub enum FontChange {
+    /// Keep the current font, as in `\text` or `\mbox`.
+    Inherit,
+    Bold,
+    Italic,
+    Upright,
+    Roman,
+    SansSerif,
+    Monospace,
+    /// Reset to the upright, medium roman font.
+    Normal,
+    /// Switch between italic and upright.
+    Emphasis,
+}
+
+/// The font family of a text transform, without the series and the shape.
+#[derive(Clone, Copy, PartialEq)]
+enum Family {
+    Roman,
+    SansSerif,
+    Monospace,
+    Script,
+    Fraktur,
+    DoubleStruck,
+}
+
+impl FontChange {
+    /// Apply the font change to the current transform.
+    ///
+    /// `None` stands for the upright, medium roman font. If Unicode has no
+    /// alphabet for the resulting combination (e.g. bold monospace), the bold
+    /// or italic part is dropped.
+    pub fn apply(self, current: Option<TextTransform>) -> Option<TextTransform> {
+        let (mut family, mut bold, mut italic) = match current {
+            None => (Family::Roman, false, false),
+            Some(tf) => match tf {
+                TextTransform::Bold | TextTransform::BoldUprightLatin => {
+                    (Family::Roman, true, false)
+                }
+                TextTransform::Italic => (Family::Roman, false, true),
+                TextTransform::BoldItalic | TextTransform::ExplicitBoldItalic => {
+                    (Family::Roman, true, true)
+                }
+                TextTransform::SansSerif => (Family::SansSerif, false, false),
+                TextTransform::BoldSansSerif | TextTransform::SansSerifBoldUprightLatin => {
+                    (Family::SansSerif, true, false)
+                }
+                TextTransform::SansSerifItalic => (Family::SansSerif, false, true),
+                TextTransform::SansSerifBoldItalic => (Family::SansSerif, true, true),
+                TextTransform::Monospace => (Family::Monospace, false, false),
+                TextTransform::Script
+                | TextTransform::ScriptChancery
+                | TextTransform::ScriptRoundhand => (Family::Script, false, false),
+                TextTransform::BoldScript
+                | TextTransform::BoldScriptChancery
+                | TextTransform::BoldScriptRoundhand => (Family::Script, true, false),
+                TextTransform::Fraktur => (Family::Fraktur, false, false),
+                TextTransform::BoldFraktur => (Family::Fraktur, true, false),
+                TextTransform::DoubleStruck => (Family::DoubleStruck, false, false),
+                TextTransform::Initial
+                | TextTransform::Looped
+                | TextTransform::Stretched
+                | TextTransform::Tailed => {
+                    // The Arabic alphabets have no bold or italic variants.
+                    return match self {
+                        FontChange::Normal => None,
+                        _ => current,
+                    };
+                }
+            },
+        };
+        match self {
+            FontChange::Inherit => return current,
+            FontChange::Normal => return None,
+            FontChange::Bold => bold = true,
+            FontChange::Italic => italic = true,
+            FontChange::Upright => italic = false,
+            FontChange::Emphasis => italic = !italic,
+            FontChange::Roman => family = Family::Roman,
+            FontChange::SansSerif => family = Family::SansSerif,
+            FontChange::Monospace => family = Family::Monospace,
+        }
+        match (family, bold, italic) {
+            (Family::Roman, false, false) => None,
+            (Family::Roman, true, false) => Some(TextTransform::Bold),
+            (Family::Roman, false, true) => Some(TextTransform::Italic),
+            (Family::Roman, true, true) => Some(TextTransform::BoldItalic),
+            (Family::SansSerif, false, false) => Some(TextTransform::SansSerif),
+            (Family::SansSerif, true, false) => Some(TextTransform::BoldSansSerif),
+            (Family::SansSerif, false, true) => Some(TextTransform::SansSerifItalic),
+            (Family::SansSerif, true, true) => Some(TextTransform::SansSerifBoldItalic),
+            (Family::Monospace, _, _) => Some(TextTransform::Monospace),
+            (Family::Script, false, _) => Some(TextTransform::Script),
+            (Family::Script, true, _) => Some(TextTransform::BoldScript),
+            (Family::Fraktur, false, _) => Some(TextTransform::Fraktur),
+            (Family::Fraktur, true, _) => Some(TextTransform::BoldFraktur),
+            (Family::DoubleStruck, _, _) => Some(TextTransform::DoubleStruck),
+        }
+    }
 }
 
 #[inline]
@@ -138,16 +324,51 @@ fn add_offset(c: char, offset: u32) -> char {
     unsafe { char::from_u32_unchecked(c as u32 + offset) }
 }
 
+fn is_greek(c: char) -> bool {
+    matches!(c, 'Α'..='Ω' | 'α'..='ω' | 'ϴ' | 'ϵ' | 'ϑ' | 'ϰ' | 'ϕ' | 'ϱ' | 'ϖ')
+}
+
+// The tables below follow the character names of the Mathematical Alphanumeric
+// Symbols block (U+1D400..U+1D7FF) in UnicodeData.txt; the holes of that block
+// are filled in from Letterlike Symbols (U+2100..U+214F).
 impl TextTransform {
+    /// The variation selector that has to follow the transformed character.
+    ///
+    /// Unicode distinguishes the chancery and roundhand forms of the script capitals
+    /// with the standardized variation sequences of U+FE00 and U+FE01.
+    pub fn variation_selector(&self, c: char) -> Option<char> {
+        match (self, c) {
+            (TextTransform::ScriptChancery, 'A'..='Z') => Some('\u{FE00}'),
+            (TextTransform::ScriptRoundhand, 'A'..='Z') => Some('\u{FE01}'),
+            _ => None,
+        }
+    }
+
     #[allow(clippy::manual_is_ascii_check)]
     pub fn transform(&self, c: char, is_normal: bool) -> char {
-        let tf = if is_normal && matches!(self, TextTransform::BoldItalic) {
-            &TextTransform::Bold
-        } else {
-            self
+        let tf = match self {
+            TextTransform::BoldItalic if is_normal => &TextTransform::Bold,
+            TextTransform::ExplicitBoldItalic => &TextTransform::BoldItalic,
+            TextTransform::BoldUprightLatin => {
+                if !is_normal && is_greek(c) {
+                    &TextTransform::BoldItalic
+                } else {
+                    &TextTransform::Bold
+                }
+            }
+            TextTransform::SansSerifBoldUprightLatin => {
+                if !is_normal && is_greek(c) {
+                    &TextTransform::SansSerifBoldItalic
+                } else {
+                    &TextTransform::BoldSansSerif
+                }
+            }
+            _ => self,
         };
         match tf {
-            TextTransform::BoldScript => match c {
+            TextTransform::BoldScript
+            | TextTransform::BoldScriptChancery
+            | TextTransform::BoldScriptRoundhand => match c {
                 'A'..='Z' => add_offset(c, 0x1D48F),
                 'a'..='z' => add_offset(c, 0x1D489),
                 _ => c,
@@ -188,14 +409,17 @@ impl TextTransform {
             },
             TextTransform::Fraktur => match c {
                 'A'..='B' | 'D'..='G' | 'J'..='Q' | 'S'..='Y' => add_offset(c, 0x1D4C3),
-                'H'..='I' => add_offset(c, 0x20C4),
                 'a'..='z' => add_offset(c, 0x1D4BD),
                 'C' => 'ℭ',
+                'H' => 'ℌ',
+                'I' => 'ℑ',
                 'R' => 'ℜ',
                 'Z' => 'ℨ',
                 _ => c,
             },
-            TextTransform::Script => match c {
+            TextTransform::Script
+            | TextTransform::ScriptChancery
+            | TextTransform::ScriptRoundhand => match c {
                 'A' | 'C'..='D' | 'G' | 'J'..='K' | 'N'..='Q' | 'S'..='Z' => add_offset(c, 0x1D45B),
                 'E'..='F' => add_offset(c, 0x20EB),
                 'a'..='d' | 'f' | 'h'..='n' | 'p'..='z' => add_offset(c, 0x1D455),
@@ -275,6 +499,31 @@ impl TextTransform {
                 'N' => 'ℕ',
                 'R' => 'ℝ',
                 'Z' => 'ℤ',
+                'ب' => '𞺡',
+                'ت' => '𞺵',
+                'ث' => '𞺶',
+                'ج' => '𞺢',
+                'ح' => '𞺧',
+                'خ' => '𞺷',
+                'د' => '𞺣',
+                'ذ' => '𞺸',
+                'ر' => '𞺳',
+                'ز' => '𞺦',
+                'س' => '𞺮',
+                'ش' => '𞺴',
+                'ص' => '𞺱',
+                'ض' => '𞺹',
+                'ط' => '𞺨',
+                'ظ' => '𞺺',
+                'ع' => '𞺯',
+                'غ' => '𞺻',
+                'ف' => '𞺰',
+                'ق' => '𞺲',
+                'ل' => '𞺫',
+                'م' => '𞺬',
+                'ن' => '𞺭',
+                'و' => '𞺥',
+                'ي' => '𞺩',
                 _ => c,
             },
             TextTransform::Italic => match c {
@@ -296,13 +545,114 @@ impl TextTransform {
                 'ϖ' => '𝜛',
                 _ => c,
             },
+            TextTransform::Initial => match c {
+                'ب' => '𞸡',
+                'ت' => '𞸵',
+                'ث' => '𞸶',
+                'ج' => '𞸢',
+                'ح' => '𞸧',
+                'خ' => '𞸷',
+                'س' => '𞸮',
+                'ش' => '𞸴',
+                'ص' => '𞸱',
+                'ض' => '𞸹',
+                'ع' => '𞸯',
+                'غ' => '𞸻',
+                'ف' => '𞸰',
+                'ق' => '𞸲',
+                'ك' => '𞸪',
+                'ل' => '𞸫',
+                'م' => '𞸬',
+                'ن' => '𞸭',
+                'ه' => '𞸤',
+                'ي' => '𞸩',
+                _ => c,
+            },
+            TextTransform::Looped => match c {
+                'ا' => '𞺀',
+                'ب' => '𞺁',
+                'ت' => '𞺕',
+                'ث' => '𞺖',
+                'ج' => '𞺂',
+                'ح' => '𞺇',
+                'خ' => '𞺗',
+                'د' => '𞺃',
+                'ذ' => '𞺘',
+                'ر' => '𞺓',
+                'ز' => '𞺆',
+                'س' => '𞺎',
+                'ش' => '𞺔',
+                'ص' => '𞺑',
+                'ض' => '𞺙',
+                'ط' => '𞺈',
+                'ظ' => '𞺚',
+                'ع' => '𞺏',
+                'غ' => '𞺛',
+                'ف' => '𞺐',
+                'ق' => '𞺒',
+                'ل' => '𞺋',
+                'م' => '𞺌',
+                'ن' => '𞺍',
+                'ه' => '𞺄',
+                'و' => '𞺅',
+                'ي' => '𞺉',
+                _ => c,
+            },
+            TextTransform::Stretched => match c {
+                'ب' => '𞹡',
+                'ت' => '𞹵',
+                'ث' => '𞹶',
+                'ج' => '𞹢',
+                'ح' => '𞹧',
+                'خ' => '𞹷',
+                'س' => '𞹮',
+                'ش' => '𞹴',
+                'ص' => '𞹱',
+                'ض' => '𞹹',
+                'ط' => '𞹨',
+                'ظ' => '𞹺',
+                'ع' => '𞹯',
+                'غ' => '𞹻',
+                'ف' => '𞹰',
+                'ق' => '𞹲',
+                'ك' => '𞹪',
+                'م' => '𞹬',
+                'ن' => '𞹭',
+                'ه' => '𞹤',
+                'ي' => '𞹩',
+                'ٮ' => '𞹼',
+                'ڡ' => '𞹾',
+                _ => c,
+            },
+            TextTransform::Tailed => match c {
+                'ج' => '𞹂',
+                'ح' => '𞹇',
+                'خ' => '𞹗',
+                'س' => '𞹎',
+                'ش' => '𞹔',
+                'ص' => '𞹑',
+                'ض' => '𞹙',
+                'ع' => '𞹏',
+                'غ' => '𞹛',
+                'ق' => '𞹒',
+                'ل' => '𞹋',
+                'ن' => '𞹍',
+                'ي' => '𞹉',
+                'ٯ' => '𞹟',
+                'ں' => '𞹝',
+                _ => c,
+            },
+            // These were resolved to one of the other transforms above.
+            TextTransform::BoldUprightLatin
+            | TextTransform::ExplicitBoldItalic
+            | TextTransform::SansSerifBoldUprightLatin => unreachable!(),
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{MathVariant, TextTransform};
+    use super::{FontChange, MathVariant, TextTransform};
 
     #[test]
     fn transform_test() {
@@ -310,7 +660,12 @@ mod tests {
             ('G', TextTransform::BoldScript, '𝓖'),
             ('H', TextTransform::Italic, '𝐻'),
             ('X', TextTransform::Fraktur, '𝔛'),
+            ('I', TextTransform::Fraktur, 'ℑ'),
             ('S', TextTransform::Script, '𝒮'),
+            ('S', TextTransform::ScriptChancery, '𝒮'),
+            ('B', TextTransform::ScriptRoundhand, 'ℬ'),
+            ('C', TextTransform::BoldScriptChancery, '𝓒'),
+            ('C', TextTransform::BoldScriptRoundhand, '𝓒'),
             ('f', TextTransform::Bold, '𝐟'),
             ('g', TextTransform::Bold, '𝐠'),
             ('o', TextTransform::DoubleStruck, '𝕠'),
@@ -342,6 +697,181 @@ mod tests {
         }
     }
 
+    #[test]
+    fn alphanumeric_coverage_test() {
+        use std::collections::HashSet;
+
+        let transforms = [
+            TextTransform::Bold,
+            TextTransform::BoldFraktur,
+            TextTransform::BoldItalic,
+            TextTransform::BoldSansSerif,
+            TextTransform::BoldScript,
+            TextTransform::DoubleStruck,
+            TextTransform::Fraktur,
+            TextTransform::Italic,
+            TextTransform::Monospace,
+            TextTransform::SansSerif,
+            TextTransform::SansSerifBoldItalic,
+            TextTransform::SansSerifItalic,
+            TextTransform::Script,
+        ];
+        let sources = ('A'..='Z')
+            .chain('a'..='z')
+            .chain('0'..='9')
+            .chain(('Α'..='Ω').filter(|&c| c != '\u{3A2}'))
+            .chain('α'..='ω')
+            .chain("ϴ∇∂ϵϑϰϕϱϖϜϝıȷ".chars())
+            .collect::<Vec<_>>();
+        // Code points in the block which Unicode leaves unassigned.
+        let reserved = [
+            0x1D455, 0x1D49D, 0x1D4A0, 0x1D4A1, 0x1D4A3, 0x1D4A4, 0x1D4A7, 0x1D4A8, 0x1D4AD,
+            0x1D4BA, 0x1D4BC, 0x1D4C4, 0x1D506, 0x1D50B, 0x1D50C, 0x1D515, 0x1D51D, 0x1D53A,
+            0x1D53F, 0x1D545, 0x1D547, 0x1D548, 0x1D549, 0x1D551, 0x1D6A6, 0x1D6A7, 0x1D7CC,
+            0x1D7CD,
+        ];
+
+        let mut seen = HashSet::new();
+        for tf in transforms {
+            for &c in &sources {
+                let out = tf.transform(c, false);
+                if out == c {
+                    continue;
+                }
+                let cp = out as u32;
+                assert!(
+                    (0x1D400..=0x1D7FF).contains(&cp) || (0x2100..=0x214F).contains(&cp),
+                    "{:?}({}) = U+{:X} is outside the math alphanumerics",
+                    tf,
+                    c,
+                    cp
+                );
+                assert!(!reserved.contains(&cp), "{:?}({}) is unassigned", tf, c);
+                assert!(seen.insert(out), "{:?}({}) = {} is not unique", tf, c, out);
+            }
+        }
+        for cp in (0x1D400..=0x1D7FF).filter(|cp| !reserved.contains(cp)) {
+            let c = char::from_u32(cp).unwrap();
+            assert!(seen.contains(&c), "U+{:X} ({}) is never produced", cp, c);
+        }
+        // The holes in the block are filled from the Letterlike Symbols block.
+        let letterlike: HashSet<_> = "ℎℂℊℋℌℍℐℑℒℕℙℚℛℜℝℤℨℬℭℯℰℱℳℴ".chars().collect();
+        assert_eq!(
+            seen.iter()
+                .filter(|&&c| (0x2100..=0x214F).contains(&(c as u32)))
+                .copied()
+                .collect::<HashSet<_>>(),
+            letterlike
+        );
+    }
+
+    #[test]
+    fn arabic_coverage_test() {
+        use std::collections::HashSet;
+
+        let transforms = [
+            (TextTransform::Initial, 20),
+            (TextTransform::Looped, 27),
+            (TextTransform::Stretched, 23),
+            (TextTransform::Tailed, 15),
+            (TextTransform::DoubleStruck, 25),
+        ];
+        // The Arabic letters with a form in Arabic Mathematical Alphabetic Symbols.
+        let sources = "ابتثجحخدذرزسشصضطظعغفقكلمنهويٮٯڡں";
+
+        let mut seen = HashSet::new();
+        for (tf, expected) in transforms {
+            let mut count = 0;
+            for c in sources.chars() {
+                let out = tf.transform(c, false);
+                if out == c {
+                    continue;
+                }
+                assert!(
+                    (0x1EE00..=0x1EEFF).contains(&(out as u32)),
+                    "{:?}({}) = {} is outside the Arabic math alphabets",
+                    tf,
+                    c,
+                    out
+                );
+                assert!(seen.insert(out), "{:?}({}) = {} is not unique", tf, c, out);
+                count += 1;
+            }
+            assert_eq!(count, expected, "number of letters in {:?}", tf);
+        }
+    }
+
+    #[test]
+    fn font_change_test() {
+        let problems = [
+            (None, FontChange::Bold, Some(TextTransform::Bold)),
+            (
+                Some(TextTransform::Bold),
+                FontChange::Italic,
+                Some(TextTransform::BoldItalic),
+            ),
+            (
+                Some(TextTransform::Italic),
+                FontChange::SansSerif,
+                Some(TextTransform::SansSerifItalic),
+            ),
+            (
+                Some(TextTransform::BoldSansSerif),
+                FontChange::Roman,
+                Some(TextTransform::Bold),
+            ),
+            (
+                Some(TextTransform::SansSerifBoldItalic),
+                FontChange::Upright,
+                Some(TextTransform::BoldSansSerif),
+            ),
+            (None, FontChange::Emphasis, Some(TextTransform::Italic)),
+            (Some(TextTransform::Italic), FontChange::Emphasis, None),
+            (
+                Some(TextTransform::Monospace),
+                FontChange::Bold,
+                Some(TextTransform::Monospace),
+            ),
+            (Some(TextTransform::BoldItalic), FontChange::Normal, None),
+            (
+                Some(TextTransform::Fraktur),
+                FontChange::Inherit,
+                Some(TextTransform::Fraktur),
+            ),
+        ];
+        for (current, change, target) in problems.into_iter() {
+            assert_eq!(
+                target,
+                change.apply(current),
+                "executed: {:?} on {:?}",
+                change,
+                current
+            );
+        }
+    }
+
+    #[test]
+    fn variation_selector_test() {
+        assert_eq!(
+            TextTransform::ScriptChancery.variation_selector('L'),
+            Some('\u{FE00}')
+        );
+        assert_eq!(
+            TextTransform::ScriptRoundhand.variation_selector('L'),
+            Some('\u{FE01}')
+        );
+        assert_eq!(
+            TextTransform::BoldScriptChancery.variation_selector('L'),
+            None
+        );
+        assert_eq!(
+            TextTransform::BoldScriptRoundhand.variation_selector('L'),
+            None
+        );
+        assert_eq!(TextTransform::ScriptRoundhand.variation_selector('l'), None);
+        assert_eq!(TextTransform::Script.variation_selector('L'), None);
+    }
+
     #[test]
     fn size_test() {
         assert_eq!(
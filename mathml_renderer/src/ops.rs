@@ -172,10 +172,12 @@ pub const DIVISION_SIGN: Bin = Bin('÷');
 pub const CARON: Op = Op('ˇ');
 pub const BREVE: Op = Op('˘');
 pub const DOT_ABOVE: Op = Op('˙');
+pub const RING_ABOVE: Op = Op('˚');
 
 //
 // Unicode Block: General Punctuation
 //
+pub const THIN_SPACE: char = '\u{2009}'; // not treated as operator
 pub const DOUBLE_VERTICAL_LINE: &ParenOp = &ParenOp('‖', true, Stretchy::PrePostfix);
 pub const HORIZONTAL_ELLIPSIS: Rel = Rel('…');
 pub const PRIME: Rel = Rel('′');
@@ -194,6 +196,12 @@ pub const OVERLINE: Rel = Rel('‾');
 
 pub const QUADRUPLE_PRIME: Rel = Rel('⁗');
 
+//
+// Unicode Block: Combining Diacritical Marks for Symbols
+//
+pub const COMBINING_THREE_DOTS_ABOVE: Op = Op('\u{20DB}');
+pub const COMBINING_FOUR_DOTS_ABOVE: Op = Op('\u{20DC}');
+
 //
 // Unicode Block: Arrows
 //
@@ -325,7 +333,7 @@ pub const ELEMENT_OF: Rel = Rel('∈');
 pub const NOT_AN_ELEMENT_OF: Rel = Rel('∉');
 // pub const SMALL_ELEMENT_OF: Op = Op('∊');
 pub const CONTAINS_AS_MEMBER: Rel = Rel('∋');
-// pub const DOES_NOT_CONTAIN_AS_MEMBER: Op = Op('∌');
+pub const DOES_NOT_CONTAIN_AS_MEMBER: Rel = Rel('∌');
 pub const SMALL_CONTAINS_AS_MEMBER: Rel = Rel('∍');
 // pub const END_OF_PROOF: Op = Op('∎');
 pub const N_ARY_PRODUCT: Big = Big('∏');
@@ -339,7 +347,7 @@ pub const SET_MINUS: Rel = Rel('∖');
 pub const ASTERISK_OPERATOR: Rel = Rel('∗');
 pub const RING_OPERATOR: Rel = Rel('∘');
 pub const BULLET_OPERATOR: Rel = Rel('∙');
-// pub const SQUARE_ROOT: Op = Op('√');
+pub const SQUARE_ROOT: char = '√';
 // pub const CUBE_ROOT: Op = Op('∛');
 // pub const FOURTH_ROOT: Op = Op('∜');
 pub const PROPORTIONAL_TO: Rel = Rel('∝');
@@ -384,7 +392,7 @@ pub const ASYMPTOTICALLY_EQUAL_TO: Rel = Rel('≃');
 pub const NOT_ASYMPTOTICALLY_EQUAL_TO: Rel = Rel('≄');
 pub const APPROXIMATELY_EQUAL_TO: Rel = Rel('≅');
 // pub const APPROXIMATELY_BUT_NOT_ACTUALLY_EQUAL_TO: Op = Op('≆');
-// pub const NEITHER_APPROXIMATELY_NOR_ACTUALLY_EQUAL_TO: Op = Op('≇');
+pub const NEITHER_APPROXIMATELY_NOR_ACTUALLY_EQUAL_TO: Rel = Rel('≇');
 pub const ALMOST_EQUAL_TO: Rel = Rel('≈');
 pub const NOT_ALMOST_EQUAL_TO: Rel = Rel('≉');
 pub const ALMOST_EQUAL_OR_EQUAL_TO: Rel = Rel('≊');
@@ -422,7 +430,7 @@ pub const GREATER_THAN_BUT_NOT_EQUAL_TO: Rel = Rel('≩');
 pub const MUCH_LESS_THAN: Rel = Rel('≪');
 pub const MUCH_GREATER_THAN: Rel = Rel('≫');
 pub const BETWEEN: Rel = Rel('≬');
-// pub const NOT_EQUIVALENT_TO: Op = Op('≭');
+pub const NOT_EQUIVALENT_TO: Rel = Rel('≭');
 pub const NOT_LESS_THAN: Rel = Rel('≮');
 pub const NOT_GREATER_THAN: Rel = Rel('≯');
 pub const NEITHER_LESS_THAN_NOR_EQUAL_TO: Rel = Rel('≰');
@@ -539,18 +547,18 @@ pub const EQUAL_TO_OR_PRECEDES: Rel = Rel('⋞');
 pub const EQUAL_TO_OR_SUCCEEDS: Rel = Rel('⋟');
 pub const DOES_NOT_PRECEDE_OR_EQUAL: Rel = Rel('⋠');
 pub const DOES_NOT_SUCCEED_OR_EQUAL: Rel = Rel('⋡');
-// pub const NOT_SQUARE_IMAGE_OF_OR_EQUAL_TO: Op = Op('⋢');
-// pub const NOT_SQUARE_ORIGINAL_OF_OR_EQUAL_TO: Op = Op('⋣');
+pub const NOT_SQUARE_IMAGE_OF_OR_EQUAL_TO: Rel = Rel('⋢');
+pub const NOT_SQUARE_ORIGINAL_OF_OR_EQUAL_TO: Rel = Rel('⋣');
 // pub const SQUARE_IMAGE_OF_OR_NOT_EQUAL_TO: Op = Op('⋤');
 // pub const SQUARE_ORIGINAL_OF_OR_NOT_EQUAL_TO: Op = Op('⋥');
 // pub const LESS_THAN_BUT_NOT_EQUIVALENT_TO: Op = Op('⋦');
 // pub const GREATER_THAN_BUT_NOT_EQUIVALENT_TO: Op = Op('⋧');
 pub const PRECEDES_BUT_NOT_EQUIVALENT_TO: Rel = Rel('⋨');
 pub const SUCCEEDS_BUT_NOT_EQUIVALENT_TO: Rel = Rel('⋩');
-// pub const NOT_NORMAL_SUBGROUP_OF: Op = Op('⋪');
-// pub const DOES_NOT_CONTAIN_AS_NORMAL_SUBGROUP: Op = Op('⋫');
-// pub const NOT_NORMAL_SUBGROUP_OF_OR_EQUAL_TO: Op = Op('⋬');
-// pub const DOES_NOT_CONTAIN_AS_NORMAL_SUBGROUP_OR_EQUAL: Op = Op('⋭');
+pub const NOT_NORMAL_SUBGROUP_OF: Rel = Rel('⋪');
+pub const DOES_NOT_CONTAIN_AS_NORMAL_SUBGROUP: Rel = Rel('⋫');
+pub const NOT_NORMAL_SUBGROUP_OF_OR_EQUAL_TO: Rel = Rel('⋬');
+pub const DOES_NOT_CONTAIN_AS_NORMAL_SUBGROUP_OR_EQUAL: Rel = Rel('⋭');
 pub const VERTICAL_ELLIPSIS: Rel = Rel('⋮');
 // pub const MIDLINE_HORIZONTAL_ELLIPSIS: Rel = Rel('⋯');
 // pub const UP_RIGHT_DIAGONAL_ELLIPSIS: Op = Op('⋰');
@@ -874,6 +882,23 @@ pub const SUCCEEDS_ABOVE_NOT_ALMOST_EQUAL_TO: Rel = Rel('⪺');
 // pub const SUPERSET_OF_ABOVE_ALMOST_EQUAL_TO: Op = Op('⫊');
 pub const SUBSET_OF_ABOVE_NOT_EQUAL_TO: Rel = Rel('⫋');
 pub const SUPERSET_OF_ABOVE_NOT_EQUAL_TO: Rel = Rel('⫌');
+// pub const SQUARE_LEFT_OPEN_BOX_OPERATOR: Op = Op('⫍');
+// pub const SQUARE_RIGHT_OPEN_BOX_OPERATOR: Op = Op('⫎');
+// pub const CLOSED_SUBSET: Op = Op('⫏');
+// pub const CLOSED_SUPERSET: Op = Op('⫐');
+// pub const CLOSED_SUBSET_OR_EQUAL_TO: Op = Op('⫑');
+// pub const CLOSED_SUPERSET_OR_EQUAL_TO: Op = Op('⫒');
+// pub const SUBSET_ABOVE_SUPERSET: Op = Op('⫓');
+// pub const SUPERSET_ABOVE_SUBSET: Op = Op('⫔');
+// pub const SUBSET_ABOVE_SUBSET: Op = Op('⫕');
+// pub const SUPERSET_ABOVE_SUPERSET: Op = Op('⫖');
+// pub const SUPERSET_BESIDE_SUBSET: Op = Op('⫗');
+// pub const SUPERSET_BESIDE_AND_JOINED_BY_DASH_WITH_SUBSET: Op = Op('⫘');
+// pub const ELEMENT_OF_OPENING_DOWNWARDS: Op = Op('⫙');
+// pub const PITCHFORK_WITH_TEE_TOP: Op = Op('⫚');
+// pub const TRANSVERSAL_INTERSECTION: Op = Op('⫛');
+pub const FORKING: Rel = Rel('⫝̸');
+pub const NONFORKING: Rel = Rel('⫝');
 
 //
 // Unicode Block: Small Form Variants